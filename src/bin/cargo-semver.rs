@@ -4,15 +4,16 @@
 extern crate rustc_session;
 
 use cargo::core::{FeatureValue, Package, PackageId, Source, SourceId, Workspace};
+use cargo::ops::{CompileFilter, FilterRule, LibRule, Packages};
 use cargo::sources::RegistrySource;
 use cargo::util::interning::InternedString;
 use curl::easy::Easy;
 use log::debug;
 use rustc_session::getopts;
-use serde::Deserialize;
-use std::collections::HashSet;
+use serde::{de::DeserializeOwned, Deserialize};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::{
-    env, io,
+    env, fs, io,
     io::Write,
     path::{Path, PathBuf},
     process::{Command, Stdio},
@@ -99,7 +100,9 @@ fn run(config: &cargo::Config, matches: &getopts::Matches) -> Result<()> {
 
     let explain = matches.opt_present("e");
     let compact = matches.opt_present("compact");
-    let json = matches.opt_present("json");
+    let format = matches.opt_str("format");
+    let json = matches.opt_present("json") || format.as_deref() == Some("json");
+    let sarif = format.as_deref() == Some("sarif");
 
     // Obtain WorkInfo for the "current"
     let current = if let Some(name_and_version) = matches.opt_str("C") {
@@ -189,10 +192,43 @@ fn run(config: &cargo::Config, matches: &getopts::Matches) -> Result<()> {
         (work_info, stable_crate.max_version.clone())
     };
 
+    // the stable version may be published under a different name, e.g. if the crate was
+    // renamed or forked
+    let stable_name = stable.package.name().to_owned();
+
     let (current_rlib, current_deps_output) =
         current.rlib_and_dep_output(config, &name, true, matches)?;
     let (stable_rlib, stable_deps_output) =
-        stable.rlib_and_dep_output(config, &name, false, matches)?;
+        stable.rlib_and_dep_output(config, &stable_name, false, matches)?;
+    let current_dep_versions = current.dependency_versions()?;
+
+    // exclusions are taken from the command line and the current version's manifest
+    let mut excludes = matches.opt_strs("exclude");
+    excludes.extend(current.metadata_strs("exclude")?);
+    let stable_dep_versions = stable.dependency_versions()?;
+
+    // the policy given on the command line takes precedence over the current version's manifest
+    let policy = if matches.opt_present("a") {
+        "api-guidelines".to_owned()
+    } else if let Some(policy) = matches.opt_str("policy") {
+        policy
+    } else {
+        current
+            .metadata::<Option<String>>("policy", "a string")?
+            .unwrap_or_else(|| "rfc1105".to_owned())
+    };
+
+    // lint levels are taken from the current version's manifest first, so that the command
+    // line takes precedence, with denials overriding warnings overriding allowances
+    let mut lints = current.metadata_lints()?;
+    for (flag, level) in &[("A", "allow"), ("W", "warn"), ("D", "deny")] {
+        lints.extend(
+            matches
+                .opt_strs(flag)
+                .into_iter()
+                .map(|lint| format!("{}={}", lint, level)),
+        );
+    }
 
     if matches.opt_present("d") {
         println!(
@@ -223,18 +259,55 @@ fn run(config: &cargo::Config, matches: &getopts::Matches) -> Result<()> {
         .arg("-")
         .stdin(Stdio::piped())
         .env("RUST_SEMVER_CRATE_VERSION", stable_version)
+        .env("RUST_SEMVER_OLD_NAME", &*stable_name)
+        .env("RUST_SEMVER_NEW_NAME", &*name)
         .env("RUST_SEMVER_VERBOSE", format!("{}", explain))
         .env("RUST_SEMVER_COMPACT", format!("{}", compact))
         .env("RUST_SEMVER_JSON", format!("{}", json))
+        .env("RUST_SEMVER_SARIF", format!("{}", sarif))
+        .env("RUST_SEMVER_OLD_DEPS", stable_dep_versions)
+        .env("RUST_SEMVER_NEW_DEPS", current_dep_versions)
+        .env("RUST_SEMVER_POLICY", policy)
+        .env(
+            "RUST_SEMVER_CONFORMANCE",
+            format!("{}", matches.opt_present("conformance")),
+        )
+        .env("RUST_SEMVER_PATHS", matches.opt_strs("path").join(","))
+        .env("RUST_SEMVER_EXCLUDE", excludes.join(","))
+        .env("RUST_SEMVER_LINTS", lints.join(","))
         .env(
-            "RUST_SEMVER_API_GUIDELINES",
-            if matches.opt_present("a") {
-                "true"
-            } else {
-                "false"
-            },
+            "RUST_SEMVER_DOC_HIDDEN_PRIVATE",
+            format!("{}", matches.opt_present("doc-hidden-private")),
+        )
+        .env(
+            "RUST_SEMVER_EXIT_NON_BREAKING",
+            format!("{}", matches.opt_present("exit-non-breaking")),
         );
 
+    if let Some(path) = matches.opt_str("write-baseline") {
+        child.env("RUST_SEMVER_WRITE_BASELINE", path);
+    }
+
+    if let Some(path) = matches.opt_str("baseline") {
+        child.env("RUST_SEMVER_BASELINE", path);
+    }
+
+    if matches.opt_present("check") {
+        child.env(
+            "RUST_SEMVER_VERIFY_VERSION",
+            current.package.version().to_string(),
+        );
+    }
+
+    // the suggested version is passed back in a file, so that the manifest can be updated
+    let version_file = if matches.opt_present("bump") {
+        let path = env::temp_dir().join(format!("semverver-version-{}", std::process::id()));
+        child.env("RUST_SEMVER_WRITE_VERSION", &path);
+        Some(path)
+    } else {
+        None
+    };
+
     let mut child = child
         .spawn()
         .map_err(|e| anyhow::Error::msg(format!("could not spawn rustc: {}", e)))?;
@@ -258,10 +331,411 @@ fn run(config: &cargo::Config, matches: &getopts::Matches) -> Result<()> {
         .wait()
         .map_err(|e| anyhow::Error::msg(format!("failed to wait for rustc: {}", e)))?;
 
-    if exit_status.success() {
-        Ok(())
-    } else {
-        Err(anyhow::Error::msg("rustc-semverver errored".to_owned()))
+    if let Some(version_file) = version_file {
+        // the suggested version is only written if the analysis succeeded, so breaking
+        // changes don't count as failures here
+        let suggested = fs::read_to_string(&version_file);
+        let _ = fs::remove_file(&version_file);
+
+        return match suggested {
+            Ok(version) => bump_version(&current, &version, matches),
+            Err(_) => Err(anyhow::Error::msg("rustc-semverver errored".to_owned())),
+        };
+    }
+
+    match exit_status.code() {
+        Some(0) => Ok(()),
+        // the exit status signifies the changes found or an insufficient version, which have
+        // already been reported
+        Some(code @ 2..=5) => std::process::exit(code),
+        _ => Err(anyhow::Error::msg("rustc-semverver errored".to_owned())),
+    }
+}
+
+/// Set the version in the manifest of the current crate to the suggested one, and optionally
+/// update the requirements of path dependents in the same workspace to match it.
+fn bump_version(current: &WorkInfo, version: &str, matches: &getopts::Matches) -> Result<()> {
+    let package = &current.package;
+    let new_version = semver::Version::parse(version)?;
+
+    if *package.version() >= new_version {
+        println!(
+            "version {} of `{}` is sufficient, leaving the manifest untouched",
+            package.version(),
+            package.name()
+        );
+        return Ok(());
+    }
+
+    let path = package.manifest_path();
+    let content = fs::read_to_string(path)?;
+    let updated = manifest::set_package_version(&content, version).ok_or_else(|| {
+        anyhow::anyhow!("could not find the package version in `{}`", path.display())
+    })?;
+    let mut edits = vec![(path.to_owned(), content, updated)];
+
+    if matches.opt_present("bump-dependents") {
+        for member in current.workspace.members() {
+            if member.package_id() == package.package_id() {
+                continue;
+            }
+
+            let deps: BTreeSet<_> = member
+                .dependencies()
+                .iter()
+                .filter(|dep| dep.package_name() == package.name() && dep.source_id().is_path())
+                .map(|dep| dep.name_in_toml())
+                .collect();
+
+            if deps.is_empty() {
+                continue;
+            }
+
+            let path = member.manifest_path();
+            let content = fs::read_to_string(path)?;
+            let updated = deps.iter().fold(content.clone(), |updated, dep| {
+                manifest::set_dependency_requirement(&updated, dep, &new_version)
+            });
+
+            if updated != content {
+                edits.push((path.to_owned(), content, updated));
+            }
+        }
+    }
+
+    for (path, content, updated) in &edits {
+        if matches.opt_present("dry-run") {
+            manifest::print_diff(path, content, updated);
+        } else {
+            fs::write(path, updated)?;
+        }
+    }
+
+    if !matches.opt_present("dry-run") {
+        println!(
+            "bumped `{}` from {} to {}",
+            package.name(),
+            package.version(),
+            version
+        );
+    }
+
+    Ok(())
+}
+
+/// Formatting-preserving edits of manifests.
+///
+/// Only the strings holding versions are replaced, leaving everything else untouched.
+mod manifest {
+    use semver::{Version, VersionReq};
+    use std::path::Path;
+
+    /// Get the name of the table opened by a line, if any.
+    fn table_header(line: &str) -> Option<&str> {
+        let line = line.trim_start();
+        let name = line.strip_prefix("[[").or_else(|| line.strip_prefix('['))?;
+
+        name.split(']').next().map(str::trim)
+    }
+
+    /// Strip a trailing comment from a line, ignoring `#` characters in strings.
+    fn strip_comment(line: &str) -> &str {
+        let mut quote = None;
+
+        for (i, c) in line.char_indices() {
+            match quote {
+                Some(q) if c == q => quote = None,
+                Some(_) => {}
+                None if c == '"' || c == '\'' => quote = Some(c),
+                None if c == '#' => return &line[..i],
+                None => {}
+            }
+        }
+
+        line
+    }
+
+    /// Find the byte range of the string value of a key in a line, possibly in an inline
+    /// table.
+    ///
+    /// Both basic (`"..."`) and literal (`'...'`) strings are accepted.
+    fn find_string_value(line: &str, key: &str) -> Option<(usize, usize)> {
+        let line = strip_comment(line);
+        let mut offset = 0;
+
+        while let Some(pos) = line[offset..].find(key) {
+            let start = offset + pos;
+            let end = start + key.len();
+            offset = end;
+
+            let boundary = line[..start]
+                .chars()
+                .last()
+                .map_or(true, |c| c.is_whitespace() || c == '{' || c == ',');
+            if !boundary {
+                continue;
+            }
+
+            let rest = line[end..].trim_start();
+            let value = match rest.strip_prefix('=') {
+                Some(value) => value.trim_start(),
+                None => continue,
+            };
+
+            if let Some(quote) = value.chars().next().filter(|&c| c == '"' || c == '\'') {
+                let content = &value[1..];
+                let content_start = line.len() - content.len();
+                let content_end = content_start + content.find(quote)?;
+
+                return Some((content_start, content_end));
+            }
+        }
+
+        None
+    }
+
+    /// Replace the byte range of a line with the given string.
+    fn replace(line: &str, (start, end): (usize, usize), value: &str) -> String {
+        format!("{}{}{}", &line[..start], value, &line[end..])
+    }
+
+    /// Check whether a table holds dependencies.
+    fn is_dependency_table(name: &str) -> bool {
+        let name = name.rsplit('.').next().unwrap_or(name);
+        name == "dependencies"
+            || name == "dev-dependencies"
+            || name == "build-dependencies"
+            || name == "dev_dependencies"
+            || name == "build_dependencies"
+    }
+
+    /// Set the version in the `[package]` table of a manifest.
+    ///
+    /// Returns `None` if the manifest doesn't specify a version.
+    pub fn set_package_version(content: &str, version: &str) -> Option<String> {
+        let mut table = None;
+        let mut found = false;
+        let mut result = String::with_capacity(content.len());
+
+        for line in content.split_inclusive('\n') {
+            if let Some(name) = table_header(line) {
+                table = Some(name);
+            } else if !found && table == Some("package") && line.trim_start().starts_with("version")
+            {
+                if let Some(range) = find_string_value(line, "version") {
+                    result.push_str(&replace(line, range, version));
+                    found = true;
+                    continue;
+                }
+            }
+
+            result.push_str(line);
+        }
+
+        if found {
+            Some(result)
+        } else {
+            None
+        }
+    }
+
+    /// Update a version requirement to match the given version, keeping its operator.
+    ///
+    /// Returns `None` if the requirement already matches the version.
+    fn update_requirement(req: &str, version: &Version) -> Option<String> {
+        if VersionReq::parse(req).map_or(false, |req| req.matches(version)) {
+            return None;
+        }
+
+        let op: String = if req.contains(',') {
+            String::new()
+        } else {
+            req.chars()
+                .take_while(|c| !c.is_ascii_digit())
+                .filter(|c| !c.is_whitespace())
+                .collect()
+        };
+
+        Some(format!("{}{}", op, version))
+    }
+
+    /// Update the requirements of all dependencies with the given name in a manifest, so that
+    /// they match the given version.
+    pub fn set_dependency_requirement(content: &str, dep: &str, version: &Version) -> String {
+        let mut table: Option<&str> = None;
+        let mut result = String::with_capacity(content.len());
+
+        for line in content.split_inclusive('\n') {
+            if let Some(name) = table_header(line) {
+                table = Some(name);
+                result.push_str(line);
+                continue;
+            }
+
+            let range = match table {
+                // an inline table in a dependency table, i.e. `dep = { path = "..", .. }`
+                Some(name) if is_dependency_table(name) => line
+                    .trim_start()
+                    .strip_prefix(dep)
+                    .filter(|rest| rest.trim_start().starts_with('='))
+                    .and_then(|rest| {
+                        let offset = line.len() - rest.len();
+                        find_string_value(&line[offset..], "version")
+                            .map(|(start, end)| (offset + start, offset + end))
+                    }),
+                // a dependency table of its own, i.e. `[dependencies.dep]`
+                Some(name)
+                    if name.rsplit_once('.').map_or(false, |(table, name)| {
+                        is_dependency_table(table) && name == dep
+                    }) =>
+                {
+                    Some(line)
+                        .filter(|line| line.trim_start().starts_with("version"))
+                        .and_then(|line| find_string_value(line, "version"))
+                }
+                _ => None,
+            };
+
+            match range.and_then(|range| {
+                update_requirement(&line[range.0..range.1], version).map(|req| (range, req))
+            }) {
+                Some((range, req)) => result.push_str(&replace(line, range, &req)),
+                None => result.push_str(line),
+            }
+        }
+
+        result
+    }
+
+    /// Print the lines changed by an edit of a manifest.
+    pub fn print_diff(path: &Path, old: &str, new: &str) {
+        println!("--- {}", path.display());
+        println!("+++ {}", path.display());
+
+        for (i, (old_line, new_line)) in old.lines().zip(new.lines()).enumerate() {
+            if old_line != new_line {
+                println!("@@ -{} +{} @@", i + 1, i + 1);
+                println!("-{}", old_line);
+                println!("+{}", new_line);
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn version(v: &str) -> Version {
+            Version::parse(v).unwrap()
+        }
+
+        #[test]
+        fn package_version() {
+            let content = "[package]\nname = \"foo\"\nversion = \"0.1.0\" # bumped\n";
+            assert_eq!(
+                set_package_version(content, "0.2.0").as_deref(),
+                Some("[package]\nname = \"foo\"\nversion = \"0.2.0\" # bumped\n")
+            );
+        }
+
+        #[test]
+        fn package_version_single_quoted() {
+            let content = "[package]\nname = 'foo'\nversion = '1.0.0'\n";
+            assert_eq!(
+                set_package_version(content, "2.0.0").as_deref(),
+                Some("[package]\nname = 'foo'\nversion = '2.0.0'\n")
+            );
+        }
+
+        #[test]
+        fn package_version_missing() {
+            let content = "[package]\nname = \"foo\"\n# version = \"0.1.0\"\n\n\
+                           [dependencies]\nbar = { version = \"1.0\" }\n";
+            assert_eq!(set_package_version(content, "0.2.0"), None);
+        }
+
+        #[test]
+        fn inline_table() {
+            let content = "[dependencies]\nfoo-bar = \"0.1\"\nfoo = { path = \"../foo\", \
+                           version = \"0.1\" }\n";
+            assert_eq!(
+                set_dependency_requirement(content, "foo", &version("0.2.0")),
+                "[dependencies]\nfoo-bar = \"0.1\"\nfoo = { path = \"../foo\", \
+                 version = \"0.2.0\" }\n"
+            );
+        }
+
+        #[test]
+        fn dependency_table() {
+            let content = "[dev-dependencies.foo]\npath = \"../foo\"\nversion = '0.1'\n";
+            assert_eq!(
+                set_dependency_requirement(content, "foo", &version("1.0.0")),
+                "[dev-dependencies.foo]\npath = \"../foo\"\nversion = '1.0.0'\n"
+            );
+        }
+
+        #[test]
+        fn target_dependencies() {
+            let content = "[target.'cfg(unix)'.dependencies]\n\
+                           foo = { version = \"0.1\", path = \"../foo\" }\n";
+            assert_eq!(
+                set_dependency_requirement(content, "foo", &version("0.2.0")),
+                "[target.'cfg(unix)'.dependencies]\n\
+                 foo = { version = \"0.2.0\", path = \"../foo\" }\n"
+            );
+        }
+
+        #[test]
+        fn renamed_dependency() {
+            let content = "[dependencies]\n\
+                           old-foo = { package = \"foo\", version = \"0.1\", path = \"../foo\" }\n";
+            assert_eq!(
+                set_dependency_requirement(content, "old-foo", &version("0.2.0")),
+                "[dependencies]\n\
+                 old-foo = { package = \"foo\", version = \"0.2.0\", path = \"../foo\" }\n"
+            );
+        }
+
+        #[test]
+        fn comments() {
+            let content = "[dependencies]\n# foo = { version = \"0.1\" }\n\
+                           foo = { path = \"../foo\" } # version = \"0.1\"\n";
+            assert_eq!(
+                set_dependency_requirement(content, "foo", &version("0.2.0")),
+                content
+            );
+        }
+
+        #[test]
+        fn requirement_operators() {
+            let content = "[dependencies]\nfoo = { version = \"~0.1.2\" }\n\n\
+                           [dev-dependencies]\nfoo = { version = \"=0.1.2\" }\n\n\
+                           [build-dependencies]\nfoo = { version = \">=0.1, <0.2\" }\n";
+            assert_eq!(
+                set_dependency_requirement(content, "foo", &version("0.2.0")),
+                "[dependencies]\nfoo = { version = \"~0.2.0\" }\n\n\
+                 [dev-dependencies]\nfoo = { version = \"=0.2.0\" }\n\n\
+                 [build-dependencies]\nfoo = { version = \"0.2.0\" }\n"
+            );
+        }
+
+        #[test]
+        fn requirement_already_matching() {
+            let content = "[dependencies]\nfoo = { version = \"0.1\", path = \"../foo\" }\n";
+            assert_eq!(
+                set_dependency_requirement(content, "foo", &version("0.1.5")),
+                content
+            );
+        }
+
+        #[test]
+        fn requirement_missing() {
+            let content = "[dependencies]\nfoo = { path = \"../foo\" }\n";
+            assert_eq!(
+                set_dependency_requirement(content, "foo", &version("0.2.0")),
+                content
+            );
+        }
     }
 }
 
@@ -291,7 +765,21 @@ mod cli {
         opts.optflag(
             "a",
             "api-guidelines",
-            "report only changes that are breaking according to the API-guidelines",
+            "report only changes that are breaking according to the API-guidelines, \
+             same as `--policy api-guidelines`",
+        );
+        opts.optopt(
+            "",
+            "policy",
+            "categorize changes according to the given policy, one of `rfc1105` (default), \
+             `strict`, `api-guidelines` and `lenient`",
+            "POLICY",
+        );
+        opts.optflag(
+            "",
+            "conformance",
+            "check whether the current crate is a drop-in replacement for the stable \
+             crate's API, ignoring additions",
         );
         opts.optopt(
             "",
@@ -315,6 +803,12 @@ mod cli {
             "json",
             "Output a JSON-formatted description of all collected data on stdout.",
         );
+        opts.optopt(
+            "",
+            "format",
+            "output format, one of `text` (default), `json` (same as `-j`) and `sarif`",
+            "FORMAT",
+        );
         opts.optopt(
             "s",
             "stable-path",
@@ -341,6 +835,79 @@ mod cli {
         );
         opts.optflag("", "offline", "Run without accessing the network.");
         opts.optopt("", "target", "Build for the target triple", "<TRIPLE>");
+        opts.optmulti(
+            "",
+            "path",
+            "only check the module or item with the given path, e.g. `net::Socket`",
+            "PATH",
+        );
+        opts.optmulti(
+            "",
+            "exclude",
+            "skip items with paths matching the given pattern, e.g. `experimental::*`",
+            "PATTERN",
+        );
+        opts.optflag(
+            "",
+            "doc-hidden-private",
+            "treat `#[doc(hidden)]` items and everything beneath them as private",
+        );
+        opts.optmulti(
+            "W",
+            "warn",
+            "report changes of the given lint as technically breaking, e.g. `semver::field_added`",
+            "LINT",
+        );
+        opts.optmulti(
+            "D",
+            "deny",
+            "report changes of the given lint as breaking",
+            "LINT",
+        );
+        opts.optmulti(
+            "A",
+            "allow",
+            "don't report changes of the given lint",
+            "LINT",
+        );
+        opts.optflag(
+            "",
+            "check",
+            "check that the current crate's version is sufficient given the changes, \
+             exiting with status 5 if it isn't",
+        );
+        opts.optflag(
+            "",
+            "exit-non-breaking",
+            "exit with status 2 instead of 0 if the most severe changes are non-breaking",
+        );
+        opts.optflag(
+            "",
+            "bump",
+            "set the current crate's version in its manifest to the suggested one",
+        );
+        opts.optflag(
+            "",
+            "bump-dependents",
+            "with `--bump`, also update the requirements of path dependents in the workspace",
+        );
+        opts.optflag(
+            "",
+            "dry-run",
+            "with `--bump`, print the changes to the manifests instead of writing them",
+        );
+        opts.optopt(
+            "",
+            "write-baseline",
+            "write the reported changes to the given baseline file",
+            "FILE",
+        );
+        opts.optopt(
+            "",
+            "baseline",
+            "don't report the changes recorded in the given baseline file",
+            "FILE",
+        );
         opts
     }
 
@@ -368,6 +935,51 @@ mod cli {
             return Err(anyhow::Error::msg(msg.to_owned()));
         }
 
+        if matches.opt_present("check") && matches.opt_present("conformance") {
+            let msg = "at most one of `--check` and `--conformance` allowed";
+            return Err(anyhow::Error::msg(msg.to_owned()));
+        }
+
+        if matches.opt_present("bump")
+            && (matches.opt_present("check")
+                || matches.opt_present("conformance")
+                || matches.opt_present("C"))
+        {
+            let msg = "`--bump` can't be combined with `--check`, `--conformance` or \
+                       `-C,--current-pkg`";
+            return Err(anyhow::Error::msg(msg.to_owned()));
+        }
+
+        if !matches.opt_present("bump")
+            && (matches.opt_present("bump-dependents") || matches.opt_present("dry-run"))
+        {
+            let msg = "`--bump-dependents` and `--dry-run` require `--bump`";
+            return Err(anyhow::Error::msg(msg.to_owned()));
+        }
+
+        match matches.opt_str("format").as_deref() {
+            None | Some("text") | Some("json") => {}
+            Some("sarif") => {
+                if matches.opt_present("json")
+                    || matches.opt_present("compact")
+                    || matches.opt_present("conformance")
+                {
+                    let msg = "`--format sarif` can't be combined with `-j,--json`, \
+                               `--compact` or `--conformance`";
+                    return Err(anyhow::Error::msg(msg.to_owned()));
+                }
+            }
+            Some(format) => {
+                let msg = format!("unknown output format `{}`", format);
+                return Err(anyhow::Error::msg(msg));
+            }
+        }
+
+        if matches.opt_present("a") && matches.opt_present("policy") {
+            let msg = "at most one of `-a,--api-guidelines` and `--policy` allowed";
+            return Err(anyhow::Error::msg(msg.to_owned()));
+        }
+
         Ok(())
     }
 
@@ -467,6 +1079,61 @@ impl<'a> WorkInfo<'a> {
         Ok(Self { package, workspace })
     }
 
+    /// Obtain the versions of the package's direct dependencies.
+    ///
+    /// The result is a comma-separated list of `name=version` pairs, as expected by
+    /// `rust-semverver` in the `RUST_SEMVER_OLD_DEPS` and `RUST_SEMVER_NEW_DEPS` variables.
+    pub fn dependency_versions(&self) -> Result<String> {
+        use cargo::core::dependency::DepKind;
+
+        let (_, resolve) = cargo::ops::resolve_ws(&self.workspace)?;
+
+        let versions: Vec<_> = resolve
+            .deps(self.package.package_id())
+            .filter(|(_, deps)| deps.iter().any(|dep| dep.kind() == DepKind::Normal))
+            .map(|(id, _)| format!("{}={}", id.name(), id.version()))
+            .collect();
+
+        Ok(versions.join(","))
+    }
+
+    /// Obtain a list of strings from the `[package.metadata.semverver]` table in the package's
+    /// manifest, if present.
+    pub fn metadata_strs(&self, key: &str) -> Result<Vec<String>> {
+        self.metadata(key, "a list of strings")
+    }
+
+    /// Obtain the `lint = "level"` pairs from the `[package.metadata.semverver.lints]` table
+    /// in the package's manifest, if present.
+    pub fn metadata_lints(&self) -> Result<Vec<String>> {
+        let lints: BTreeMap<String, String> = self.metadata("lints", "a table of strings")?;
+
+        Ok(lints
+            .into_iter()
+            .map(|(lint, level)| format!("{}={}", lint, level))
+            .collect())
+    }
+
+    /// Obtain a value from the `[package.metadata.semverver]` table in the package's manifest,
+    /// falling back to the default if not present.
+    fn metadata<T: DeserializeOwned + Default>(&self, key: &str, expected: &str) -> Result<T> {
+        let metadata = match self.package.manifest().custom_metadata() {
+            Some(metadata) => serde_json::to_value(metadata)?,
+            None => return Ok(T::default()),
+        };
+
+        match metadata.get("semverver").and_then(|table| table.get(key)) {
+            Some(value) => serde_json::from_value(value.clone()).map_err(|_| {
+                anyhow::anyhow!(
+                    "`package.metadata.semverver.{}` has to be {}",
+                    key,
+                    expected
+                )
+            }),
+            None => Ok(T::default()),
+        }
+    }
+
     /// Obtain the paths to the produced rlib and the dependency output directory.
     pub fn rlib_and_dep_output(
         &self,
@@ -504,6 +1171,19 @@ impl<'a> WorkInfo<'a> {
         opts.cli_features.all_features = matches.opt_present("all-features");
         opts.cli_features.uses_default_features = !matches.opt_present("no-default-features");
 
+        // `--cfg semverver` enables the `semverver::allow` attributes acknowledging changes. It
+        // is only passed to the library analyzed, like `cargo rustc --lib -- --cfg semverver`
+        // would, so that dependencies checking for the cfg aren't affected.
+        opts.spec = Packages::Packages(vec![name.to_owned()]);
+        opts.filter = CompileFilter::new(
+            LibRule::True,
+            FilterRule::none(),
+            FilterRule::none(),
+            FilterRule::none(),
+            FilterRule::none(),
+        );
+        opts.target_rustc_args = Some(vec!["--cfg".to_owned(), "semverver".to_owned()]);
+
         env::set_var(
             "RUSTFLAGS",
             format!("-C metadata={}", if current { "new" } else { "old" }),
@@ -8,8 +8,12 @@ use log::debug;
 use rustc_driver::{Callbacks, Compilation, RunCompiler};
 use rustc_interface::{interface, Queries};
 use rustc_span::source_map::Pos;
-use semverver::run_analysis;
+use semverver::{
+    run_analysis, AnalysisOptions, BaselineEntry, ChangeCategory, DependencyVersions, LintLevels,
+    Policy,
+};
 use std::{
+    fs,
     path::Path,
     process::{exit, Command},
 };
@@ -19,6 +23,22 @@ fn show_version() {
     println!(env!("CARGO_PKG_VERSION"));
 }
 
+/// The exit status signifying that the version of the new crate is insufficient.
+const INSUFFICIENT_VERSION: i32 = 5;
+
+/// Get the exit status signifying the most severe category of changes found.
+///
+/// Failures of the tool itself are signified by an exit status of 1.
+fn category_exit_status(max: ChangeCategory, non_breaking_exit: bool) -> i32 {
+    match max {
+        ChangeCategory::Patch => 0,
+        ChangeCategory::NonBreaking if non_breaking_exit => 2,
+        ChangeCategory::NonBreaking => 0,
+        ChangeCategory::TechnicallyBreaking => 3,
+        ChangeCategory::Breaking => 4,
+    }
+}
+
 /// Main routine.
 ///
 /// Find the sysroot before passing our args to the custom compiler driver we register.
@@ -26,122 +46,271 @@ fn main() {
     env_logger::init_from_env("RUSTC_LOG");
 
     debug!("running rust-semverver compiler driver");
-    exit(
-        {
-            use std::env;
-
-            struct SemverCallbacks;
-
-            impl Callbacks for SemverCallbacks {
-                fn after_analysis<'tcx>(&mut self, _compiler: &interface::Compiler, queries: &'tcx Queries<'tcx>) -> Compilation {
-                    debug!("running rust-semverver after_analysis callback");
-
-                    let verbose =
-                        env::var("RUST_SEMVER_VERBOSE") == Ok("true".to_string());
-                    let compact =
-                        env::var("RUST_SEMVER_COMPACT") == Ok("true".to_string());
-                    let json =
-                        env::var("RUST_SEMVER_JSON") == Ok("true".to_string());
-                    let api_guidelines =
-                        env::var("RUST_SEMVER_API_GUIDELINES") == Ok("true".to_string());
-                    let version = if let Ok(ver) = env::var("RUST_SEMVER_CRATE_VERSION") {
-                        ver
-                    } else {
-                        "no_version".to_owned()
-                    };
-
-                    queries.global_ctxt().unwrap().peek_mut().enter(|tcx| {
-                        // To select the old and new crates we look at the position of the
-                        // declaration in the source file. The first one will be the `old`
-                        // and the other will be `new`. This is unfortunately a bit hacky...
-                        // See issue #64 for details.
-
-                        let mut crates: Vec<_> = tcx
-                            .crates()
-                            .iter()
-                            .flat_map(|crate_num| {
-                                let def_id = crate_num.as_def_id();
-
-                                match tcx.extern_crate(def_id) {
-                                    Some(extern_crate) if extern_crate.is_direct() && extern_crate.span.data().lo.to_usize() > 0 =>
-                                        Some((extern_crate.span.data().lo.to_usize(), def_id)),
-                                    _ => None,
+    exit({
+        use std::env;
+
+        struct SemverCallbacks {
+            /// The exit status determined by the analysis, if it has been carried out.
+            exit_status: Option<i32>,
+        }
+
+        impl Callbacks for SemverCallbacks {
+            fn after_analysis<'tcx>(
+                &mut self,
+                _compiler: &interface::Compiler,
+                queries: &'tcx Queries<'tcx>,
+            ) -> Compilation {
+                debug!("running rust-semverver after_analysis callback");
+
+                let verbose = env::var("RUST_SEMVER_VERBOSE") == Ok("true".to_string());
+                let compact = env::var("RUST_SEMVER_COMPACT") == Ok("true".to_string());
+                let json = env::var("RUST_SEMVER_JSON") == Ok("true".to_string());
+                let sarif = env::var("RUST_SEMVER_SARIF") == Ok("true".to_string());
+                let conformance = env::var("RUST_SEMVER_CONFORMANCE") == Ok("true".to_string());
+                let version = if let Ok(ver) = env::var("RUST_SEMVER_CRATE_VERSION") {
+                    ver
+                } else {
+                    "no_version".to_owned()
+                };
+                let old_name = env::var("RUST_SEMVER_OLD_NAME").ok();
+                let new_name = env::var("RUST_SEMVER_NEW_NAME").ok();
+                let names = old_name.as_deref().zip(new_name.as_deref());
+                let list = |var| -> Vec<String> {
+                    env::var(var)
+                        .map(|list| {
+                            list.split(',')
+                                .filter(|p| !p.is_empty())
+                                .map(String::from)
+                                .collect()
+                        })
+                        .unwrap_or_default()
+                };
+                let policy = env::var("RUST_SEMVER_POLICY")
+                    .map_or_else(|_| Ok(Policy::default()), |name| Policy::parse(&name));
+                let lints = policy.and_then(|policy| {
+                    LintLevels::parse(policy, &env::var("RUST_SEMVER_LINTS").unwrap_or_default())
+                });
+                let options = AnalysisOptions {
+                    paths: list("RUST_SEMVER_PATHS"),
+                    excludes: list("RUST_SEMVER_EXCLUDE"),
+                    doc_hidden_private: env::var("RUST_SEMVER_DOC_HIDDEN_PRIVATE")
+                        == Ok("true".to_string()),
+                    lints: lints.clone().unwrap_or_default(),
+                };
+                let write_baseline = env::var("RUST_SEMVER_WRITE_BASELINE").ok();
+                let baseline = env::var("RUST_SEMVER_BASELINE").ok();
+                let verify_version = env::var("RUST_SEMVER_VERIFY_VERSION").ok();
+                let write_version = env::var("RUST_SEMVER_WRITE_VERSION").ok();
+                let non_breaking_exit =
+                    env::var("RUST_SEMVER_EXIT_NON_BREAKING") == Ok("true".to_string());
+                let exit_status = &mut self.exit_status;
+                let dependency_versions = DependencyVersions::parse(
+                    &env::var("RUST_SEMVER_OLD_DEPS").unwrap_or_default(),
+                    &env::var("RUST_SEMVER_NEW_DEPS").unwrap_or_default(),
+                );
+
+                queries.global_ctxt().unwrap().peek_mut().enter(|tcx| {
+                    if let Err(err) = &lints {
+                        tcx.sess
+                            .err(&format!("invalid policy or lint levels: {}", err));
+                        return;
+                    }
+
+                    // To select the old and new crates we look at the position of the
+                    // declaration in the source file. The first one will be the `old`
+                    // and the other will be `new`. This is unfortunately a bit hacky...
+                    // See issue #64 for details.
+
+                    let mut crates: Vec<_> = tcx
+                        .crates()
+                        .iter()
+                        .flat_map(|crate_num| {
+                            let def_id = crate_num.as_def_id();
+
+                            match tcx.extern_crate(def_id) {
+                                Some(extern_crate)
+                                    if extern_crate.is_direct()
+                                        && extern_crate.span.data().lo.to_usize() > 0 =>
+                                {
+                                    Some((extern_crate.span.data().lo.to_usize(), def_id))
                                 }
-                            })
-                            .collect();
+                                _ => None,
+                            }
+                        })
+                        .collect();
+
+                    crates.sort_by_key(|&(span_lo, _)| span_lo);
+
+                    if let [(_, old_def_id), (_, new_def_id)] = *crates.as_slice() {
+                        debug!("running semver analysis");
+                        let mut changes = run_analysis(
+                            tcx,
+                            old_def_id,
+                            new_def_id,
+                            &dependency_versions,
+                            &options,
+                        );
+
+                        if let Some(path) = write_baseline {
+                            let entries = changes.baseline(tcx);
+                            let json = serde_json::to_string_pretty(&entries).unwrap();
 
-                        crates.sort_by_key(|&(span_lo, _)| span_lo);
+                            if let Err(err) = fs::write(&path, json + "\n") {
+                                tcx.sess
+                                    .err(&format!("could not write baseline `{}`: {}", path, err));
+                            }
+                        }
+
+                        if let Some(path) = baseline {
+                            let entries: Result<Vec<BaselineEntry>, String> =
+                                fs::read_to_string(&path)
+                                    .map_err(|err| err.to_string())
+                                    .and_then(|json| {
+                                        serde_json::from_str(&json).map_err(|err| err.to_string())
+                                    });
 
-                        if let [(_, old_def_id), (_, new_def_id)] = *crates.as_slice() {
-                            debug!("running semver analysis");
-                            let changes = run_analysis(tcx, old_def_id, new_def_id);
-                            if json {
-                                changes.output_json(tcx.sess, &version);
+                            match entries {
+                                Ok(entries) => {
+                                    for entry in changes.apply_baseline(tcx, &entries) {
+                                        tcx.sess.warn(&format!(
+                                            "stale baseline entry {}: {} of `{}` no longer occurs",
+                                            entry.id, entry.change, entry.path
+                                        ));
+                                    }
+                                }
+                                Err(err) => {
+                                    tcx.sess.err(&format!(
+                                        "could not read baseline `{}`: {}",
+                                        path, err
+                                    ));
+                                }
+                            }
+                        }
+
+                        // errors up to this point are failures, rather than changes reported
+                        let failed = tcx.sess.has_errors();
+
+                        if conformance && json {
+                            changes.output_conformance_json(tcx.sess, names);
+                        } else if conformance {
+                            changes.output_conformance(tcx.sess, verbose);
+                        } else if sarif {
+                            changes.output_sarif(tcx.sess);
+                        } else if json {
+                            changes.output_json(tcx.sess, &version, names);
+                        } else {
+                            changes.output(tcx.sess, &version, names, verbose, compact);
+                        }
+
+                        if let Some(path) = write_version.filter(|_| !failed) {
+                            if let Some((new_version, _)) = changes.get_new_version(&version) {
+                                if let Err(err) = fs::write(&path, new_version) {
+                                    tcx.sess.err(&format!(
+                                        "could not write version to `{}`: {}",
+                                        path, err
+                                    ));
+                                }
                             } else {
-                                changes.output(tcx.sess, &version, verbose, compact, api_guidelines);
+                                tcx.sess
+                                    .err(&format!("could not parse `{}` as a version", version));
+                            }
+                        }
+
+                        if failed {
+                            // the exit status signifies the failure
+                        } else if let Some(new_version) = verify_version {
+                            match changes.check_version(tcx.sess, &version, &new_version) {
+                                Some(true) => *exit_status = Some(0),
+                                Some(false) => *exit_status = Some(INSUFFICIENT_VERSION),
+                                None => tcx.sess.err(&format!(
+                                    "could not parse `{}` or `{}` as versions",
+                                    version, new_version
+                                )),
                             }
+                        } else if conformance {
+                            *exit_status = Some(if changes.is_conforming() {
+                                0
+                            } else {
+                                category_exit_status(ChangeCategory::Breaking, false)
+                            });
                         } else {
-                            tcx.sess.err("could not find `old` and `new` crates");
+                            *exit_status = Some(category_exit_status(
+                                changes.max_category(),
+                                non_breaking_exit,
+                            ));
                         }
-                    });
+                    } else {
+                        tcx.sess.err("could not find `old` and `new` crates");
+                    }
+                });
 
-                    debug!("rust-semverver after_analysis callback finished!");
+                debug!("rust-semverver after_analysis callback finished!");
 
-                    Compilation::Stop
-                }
+                Compilation::Stop
             }
+        }
 
-            if env::args().any(|a| a == "--version" || a == "-V") {
-                show_version();
-                exit(0);
-            }
+        if env::args().any(|a| a == "--version" || a == "-V") {
+            show_version();
+            exit(0);
+        }
 
-            let sys_root = option_env!("SYSROOT")
-                .map(String::from)
-                .or_else(|| env::var("SYSROOT").ok())
-                .or_else(|| {
-                    let home = option_env!("RUSTUP_HOME").or(option_env!("MULTIRUST_HOME"));
-                    let toolchain = option_env!("RUSTUP_TOOLCHAIN").or(option_env!("MULTIRUST_TOOLCHAIN"));
-                    home.and_then(|home| toolchain.map(|toolchain| format!("{}/toolchains/{}", home, toolchain)))
+        let sys_root = option_env!("SYSROOT")
+            .map(String::from)
+            .or_else(|| env::var("SYSROOT").ok())
+            .or_else(|| {
+                let home = option_env!("RUSTUP_HOME").or(option_env!("MULTIRUST_HOME"));
+                let toolchain =
+                    option_env!("RUSTUP_TOOLCHAIN").or(option_env!("MULTIRUST_TOOLCHAIN"));
+                home.and_then(|home| {
+                    toolchain.map(|toolchain| format!("{}/toolchains/{}", home, toolchain))
                 })
-                .or_else(|| {
-                    Command::new("rustc")
-                        .arg("--print")
-                        .arg("sysroot")
-                        .output()
-                        .ok()
-                        .and_then(|out| String::from_utf8(out.stdout).ok())
-                        .map(|s| s.trim().to_owned())
-                })
-                .expect("need to specify SYSROOT env var during clippy compilation, or use rustup or multirust");
-
-            // Setting RUSTC_WRAPPER causes Cargo to pass 'rustc' as the first argument.
-            // We're invoking the compiler programmatically, so we ignore this/
-            let mut orig_args: Vec<String> = env::args().collect();
-            if orig_args.len() <= 1 {
-                std::process::exit(1);
-            }
+            })
+            .or_else(|| {
+                Command::new("rustc")
+                    .arg("--print")
+                    .arg("sysroot")
+                    .output()
+                    .ok()
+                    .and_then(|out| String::from_utf8(out.stdout).ok())
+                    .map(|s| s.trim().to_owned())
+            })
+            .expect(
+                "need to specify SYSROOT env var during clippy compilation, or use rustup or \
+                 multirust",
+            );
 
-            if Path::new(&orig_args[1]).file_stem() == Some("rustc".as_ref()) {
-                // we still want to be able to invoke it normally though
-                orig_args.remove(1);
-            }
+        // Setting RUSTC_WRAPPER causes Cargo to pass 'rustc' as the first argument.
+        // We're invoking the compiler programmatically, so we ignore this/
+        let mut orig_args: Vec<String> = env::args().collect();
+        if orig_args.len() <= 1 {
+            std::process::exit(1);
+        }
 
-            // this conditional check for the --sysroot flag is there so users can call
-            // `clippy_driver` directly
-            // without having to pass --sysroot or anything
-            let args: Vec<String> = if orig_args.iter().any(|s| s == "--sysroot") {
-                orig_args
-            } else {
-                orig_args
-                    .into_iter()
-                    .chain(Some("--sysroot".to_owned()))
-                    .chain(Some(sys_root))
-                    .collect()
-            };
-
-            RunCompiler::new(&args, &mut SemverCallbacks).run()
+        if Path::new(&orig_args[1]).file_stem() == Some("rustc".as_ref()) {
+            // we still want to be able to invoke it normally though
+            orig_args.remove(1);
         }
-        .map_or_else(|_| 1, |_| 0),
-    )
+
+        // this conditional check for the --sysroot flag is there so users can call
+        // `clippy_driver` directly
+        // without having to pass --sysroot or anything
+        let args: Vec<String> = if orig_args.iter().any(|s| s == "--sysroot") {
+            orig_args
+        } else {
+            orig_args
+                .into_iter()
+                .chain(Some("--sysroot".to_owned()))
+                .chain(Some(sys_root))
+                .collect()
+        };
+
+        let mut callbacks = SemverCallbacks { exit_status: None };
+        let result = RunCompiler::new(&args, &mut callbacks).run();
+
+        // errors reporting breaking changes don't count as failures
+        callbacks
+            .exit_status
+            .unwrap_or_else(|| result.map_or_else(|_| 1, |_| 0))
+    })
 }
@@ -9,20 +9,22 @@
 //! complicated by the fact that we still group changes by the item they refer to, even if it's
 //! path changes.
 
-use rustc_hir::def_id::DefId;
-use rustc_middle::ty::{error::TypeError, Predicate};
+use rustc_hir::def_id::{CrateNum, DefId};
+use rustc_middle::ty::{error::TypeError, Predicate, Ty, TyCtxt};
 use rustc_session::Session;
 use rustc_span::symbol::Symbol;
-use rustc_span::{FileName, Span};
-use semver::Version;
+use rustc_span::{FileName, Loc, Span};
+use semver::{Identifier, Version};
 use std::{
     cmp::Ordering,
-    collections::{BTreeMap, BTreeSet, HashMap},
-    fmt,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    env, fmt,
+    path::Path,
+    rc::Rc,
 };
 
 use serde::ser::{SerializeSeq, SerializeStruct, Serializer};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 /// The categories we use when analyzing changes between crate versions.
 ///
@@ -32,7 +34,7 @@ use serde::Serialize;
 /// exotic and/or unlikely scenarios, while we have a separate category for them.
 ///
 /// [1]: https://github.com/rust-lang/rfcs/blob/master/text/1105-api-evolution.md
-#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum ChangeCategory {
     /// A patch-level change - no change to the public API of a crate.
     Patch,
@@ -76,6 +78,76 @@ impl Serialize for RSymbol {
     }
 }
 
+/// Get the name of the file a source location lies in, for use in machine-readable output.
+fn file_name(loc: &Loc) -> String {
+    if let FileName::Real(ref name) = loc.file.name {
+        name.local_path().map(|p| format!("{}", p.display()))
+    } else {
+        None
+    }
+    .unwrap_or_else(|| "no file name".to_owned())
+}
+
+/// Get the URI of the file a source location lies in, relative to the working directory if
+/// possible.
+fn sarif_uri(loc: &Loc) -> String {
+    let name = file_name(loc);
+    let path = Path::new(&name);
+    let relative = env::current_dir()
+        .ok()
+        .and_then(|cwd| path.strip_prefix(cwd).ok().map(Path::to_path_buf));
+
+    match relative {
+        Some(relative) => relative.display().to_string().replace('\\', "/"),
+        None if path.is_absolute() && name.starts_with('/') => format!("file://{}", name),
+        None if path.is_absolute() => format!("file:///{}", name.replace('\\', "/")),
+        None => name.replace('\\', "/"),
+    }
+}
+
+/// Describe a span as a SARIF location, with an optional identifier and message.
+fn sarif_location(
+    session: &Session,
+    span: Span,
+    id: Option<usize>,
+    message: Option<&str>,
+) -> serde_json::Value {
+    let lo = session.source_map().lookup_char_pos(span.lo());
+    let hi = session.source_map().lookup_char_pos(span.hi());
+
+    let mut location = serde_json::json!({
+        "physicalLocation": {
+            "artifactLocation": { "uri": sarif_uri(&lo) },
+            "region": {
+                "startLine": lo.line,
+                "startColumn": lo.col.0 + 1,
+                "endLine": hi.line,
+                "endColumn": hi.col.0 + 1,
+            },
+        },
+    });
+
+    if let Some(id) = id {
+        location["id"] = id.into();
+    }
+
+    if let Some(message) = message {
+        location["message"] = serde_json::json!({ "text": message });
+    }
+
+    location
+}
+
+/// Map a change category to the level of a SARIF result.
+fn sarif_level(cat: ChangeCategory) -> &'static str {
+    match cat {
+        Patch => "none",
+        NonBreaking => "note",
+        TechnicallyBreaking => "warning",
+        Breaking => "error",
+    }
+}
+
 struct RSpan<'a>(&'a Session, &'a Span);
 
 impl<'a> Serialize for RSpan<'a> {
@@ -87,12 +159,7 @@ impl<'a> Serialize for RSpan<'a> {
         let hi = self.0.source_map().lookup_char_pos(self.1.hi());
 
         assert!(lo.file.name == hi.file.name);
-        let file_name = if let FileName::Real(ref name) = lo.file.name {
-            name.local_path().map(|p| format!("{}", p.display()))
-        } else {
-            None
-        }
-        .unwrap_or_else(|| "no file name".to_owned());
+        let file_name = file_name(&lo);
 
         let mut state = serializer.serialize_struct("Span", 5)?;
         state.serialize_field("file", &file_name)?;
@@ -155,16 +222,19 @@ pub struct PathChange {
     additions: BTreeSet<Span>,
     /// The set of spans of removed exports of the item.
     removals: BTreeSet<Span>,
+    /// The lint levels determining the categories of the changes.
+    lints: Rc<LintLevels>,
 }
 
 impl PathChange {
     /// Construct a new empty path change record for an item.
-    fn new(name: Symbol, def_span: Span) -> Self {
+    fn new(name: Symbol, def_span: Span, lints: Rc<LintLevels>) -> Self {
         Self {
             name: RSymbol(name),
             def_span,
             additions: BTreeSet::new(),
             removals: BTreeSet::new(),
+            lints,
         }
     }
 
@@ -177,15 +247,25 @@ impl PathChange {
         }
     }
 
+    /// Get the category of an added or removed export of the item.
+    fn category(&self, add: bool) -> ChangeCategory {
+        self.lints.path_category(add)
+    }
+
     /// Get the change's category.
     pub fn to_category(&self) -> ChangeCategory {
-        if !self.removals.is_empty() {
-            Breaking
-        } else if self.additions.is_empty() {
+        let removed = if self.removals.is_empty() {
             Patch
         } else {
-            TechnicallyBreaking
-        }
+            self.category(false)
+        };
+        let added = if self.additions.is_empty() {
+            Patch
+        } else {
+            self.category(true)
+        };
+
+        removed.max(added)
     }
 
     /// Get the change item's definition span.
@@ -193,6 +273,28 @@ impl PathChange {
         &self.def_span
     }
 
+    /// Describe an added or removed span of the item, as used in baseline files.
+    fn describe(&self, span: Span, add: bool) -> &'static str {
+        match (span == self.def_span, add) {
+            (true, true) => "added definition",
+            (false, true) => "added path",
+            (true, false) => "removed definition",
+            (false, false) => "removed path",
+        }
+    }
+
+    /// Explain the category of an added or removed export of an item.
+    fn explanation(add: bool) -> &'static str {
+        if add {
+            "Adding a path to an item is a non-breaking change, except in the special case of
+wildcard imports in user code, where it can cause nameclashes. Thus, the change is
+classified as \"technically breaking\"."
+        } else {
+            "Removing a path to an item is a breaking change, because user code referring to
+the item by that path doesn't compile anymore."
+        }
+    }
+
     /// Report the change in a structured manner, using rustc's error reporting capabilities.
     fn report(&self, session: &Session) {
         let cat = self.to_category();
@@ -207,19 +309,39 @@ impl PathChange {
             session.struct_span_warn(self.def_span, &msg)
         };
 
-        for removed_span in &self.removals {
-            if *removed_span == self.def_span {
-                builder.warn("removed definition (breaking)");
-            } else {
-                builder.span_warn(*removed_span, "removed path (breaking)");
+        for (spans, add) in &[(&self.removals, false), (&self.additions, true)] {
+            let cat = self.category(*add);
+            if cat == Patch {
+                continue;
+            }
+
+            for span in spans.iter() {
+                let sub_msg = format!("{} ({})", self.describe(*span, *add), cat);
+
+                match (*span == self.def_span, cat == Breaking) {
+                    (true, true) => builder.warn(&sub_msg),
+                    (true, false) => builder.note(&sub_msg),
+                    (false, true) => builder.span_warn(*span, &sub_msg),
+                    (false, false) => builder.span_note(*span, &sub_msg),
+                };
             }
         }
 
-        for added_span in &self.additions {
-            if *added_span == self.def_span {
-                builder.note("added definition (technically breaking)");
-            } else {
-                builder.span_note(*added_span, "added path (technically breaking)");
+        builder.emit();
+    }
+
+    /// Report the removals recorded in the change as paths missing from a replacement crate.
+    fn report_missing(&self, session: &Session) {
+        if self.removals.is_empty() {
+            return;
+        }
+
+        let msg = format!("`{}` is missing from the replacement", self.name.0);
+        let mut builder = session.struct_span_err(self.def_span, &msg);
+
+        for removed_span in &self.removals {
+            if *removed_span != self.def_span {
+                builder.span_warn(*removed_span, "path missing from the replacement");
             }
         }
 
@@ -270,6 +392,32 @@ impl<'a> Serialize for RPathChange<'a> {
     }
 }
 
+/// The ways a struct or variant constructor can change between crate versions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CtorChange {
+    /// A public constructor has been added.
+    Added,
+    /// A public constructor has been removed.
+    Removed,
+    /// A constructor has been made public.
+    MadePublic,
+    /// A constructor has been made private.
+    MadePrivate,
+    /// A public tuple constructor's number of arguments has changed.
+    ArityChanged { old: usize, new: usize },
+}
+
+/// The ways a function's explicit generic parameters can change so that turbofish calls break.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TurbofishChange {
+    /// The explicit generic parameters have been reordered.
+    Reordered,
+    /// An `impl Trait` argument has been introduced.
+    ImplTraitIntroduced,
+    /// The number of explicit generic parameters has changed.
+    ArityChanged { old: usize, new: usize },
+}
+
 /// The types of changes we identify between items present in both crate versions.
 #[derive(Clone, Debug)]
 pub enum ChangeType<'tcx> {
@@ -279,6 +427,9 @@ pub enum ChangeType<'tcx> {
     ItemMadePrivate,
     /// An item has changed it's kind.
     KindDifference,
+    /// A struct, enum or union has been replaced by a type alias expanding to an equivalent type,
+    /// or vice-versa.
+    AliasKindChanged { now_alias: bool },
     /// A `static` item changed it's mutablity.
     StaticMutabilityChanged { now_mut: bool },
     /// The variance of a type or region parameter has gone from invariant to co- or
@@ -294,6 +445,9 @@ pub enum ChangeType<'tcx> {
     RegionParameterAdded,
     /// A region parameter has been removed from an item.
     RegionParameterRemoved,
+    /// A function's lifetime has changed from being early-bound to being late-bound or
+    /// vice-versa.
+    RegionBindingChanged { name: Symbol, now_late_bound: bool },
     /// A possibly defaulted type parameter has been added to an item.
     TypeParameterAdded { defaulted: bool },
     /// A possibly defaulted type parameter has been removed from an item.
@@ -328,6 +482,14 @@ pub enum ChangeType<'tcx> {
         total_private: bool,
         is_enum: bool,
     },
+    /// A struct's or variant's constructor has been added, removed, or changed.
+    ///
+    /// This tracks the ability to construct (and match on) the item by name in the value
+    /// namespace, which is governed by the visibility of all its fields.
+    ConstructorChanged { change: CtorChange },
+    /// A function's explicit generic parameters have changed in a way that breaks turbofish
+    /// calls, i.e. `foo::<T>()`.
+    TurbofishBroken { change: TurbofishChange },
     /// A function has changed it's constness.
     FnConstChanged { now_const: bool },
     /// A method either gained or lost a `self` parameter.
@@ -336,10 +498,40 @@ pub enum ChangeType<'tcx> {
     TraitItemAdded { defaulted: bool, sealed_trait: bool },
     /// A trait's definition removed a possibly defaulted item.
     TraitItemRemoved { defaulted: bool },
+    /// A trait's item has been moved to one of its supertraits, keeping its signature.
+    ///
+    /// This describes the impact on callers, which only break if the supertrait isn't in scope.
+    TraitItemMovedToSupertrait { supertrait: String },
+    /// A trait's item has to be implemented as part of one of its supertraits.
+    ///
+    /// This describes the impact of a move to a supertrait on implementors of a trait that
+    /// isn't sealed.
+    TraitItemImplMovedToSupertrait { supertrait: String },
     /// A trait's definition changed it's unsafety.
     TraitUnsafetyChanged { now_unsafe: bool },
     /// An item's type has changed.
     TypeChanged { error: TypeError<'tcx> },
+    /// A function's number of parameters has changed.
+    FnArgCountChanged { old: usize, new: usize },
+    /// The type of a function's parameter has changed.
+    ///
+    /// The parameter's index is zero-based and counts a possible `self` parameter.
+    FnArgTypeChanged {
+        index: usize,
+        old: Ty<'tcx>,
+        new: Ty<'tcx>,
+    },
+    /// A function's return type has changed.
+    FnReturnTypeChanged { old: Ty<'tcx>, new: Ty<'tcx> },
+    /// The future returned by an `async fn` or a function returning `impl Future` has changed
+    /// whether it is `Send`.
+    FutureSendChanged { now_send: bool },
+    /// The future returned by an `async fn` or a function returning `impl Future` has started
+    /// or stopped capturing the lifetime of a parameter.
+    FutureCaptureChanged { param: Symbol, now_captured: bool },
+    /// The future returned by an `async fn` or a function returning `impl Future` has started
+    /// or stopped implementing a trait other than `Send`, like `Unpin` or `Sync`.
+    FutureBoundChanged { bound: String, now_bound: bool },
     /// An item's (trait) bounds have been tightened.
     BoundsTightened { pred: Predicate<'tcx> },
     /// An item's (trait) bounds have been loosened.
@@ -351,6 +543,20 @@ pub enum ChangeType<'tcx> {
         pred: Predicate<'tcx>,
         trait_def: bool,
     },
+    /// A type parameter has lost its `?Sized` relaxation, i.e. it has to be `Sized` now.
+    SizedBoundAdded { param: Symbol },
+    /// A type parameter has been relaxed using `?Sized`.
+    ///
+    /// As with other bounds, this is breaking on trait definitions.
+    SizedBoundRemoved { param: Symbol, trait_def: bool },
+    /// A dependency whose items appear in an item's public signature has changed its version.
+    ///
+    /// The versions are only known if they have been passed in by the caller.
+    PublicDependencyChanged {
+        krate: Symbol,
+        old_version: Option<String>,
+        new_version: Option<String>,
+    },
     /// A trait impl has been specialized or removed for some type(s).
     TraitImplTightened,
     /// A trait impl has been generalized or newly added for some type(s).
@@ -359,6 +565,10 @@ pub enum ChangeType<'tcx> {
     AssociatedItemAdded,
     /// An associated item has been removed from some inherent impls.
     AssociatedItemRemoved,
+    /// A method has been moved from an inherent impl to an impl of the given trait.
+    MethodMovedToTrait { trait_path: String },
+    /// A method has been moved from an impl of the given trait to an inherent impl.
+    MethodMovedFromTrait { trait_path: String },
     /// An unknown change we don't yet explicitly handle.
     Unknown,
 }
@@ -366,6 +576,89 @@ pub enum ChangeType<'tcx> {
 pub use self::ChangeType::*;
 
 impl<'tcx> ChangeType<'tcx> {
+    /// Get the name of the lint the change type is reported by.
+    pub fn lint_name(&self) -> &'static str {
+        match *self {
+            ItemMadePublic => "item_made_public",
+            ItemMadePrivate => "item_made_private",
+            KindDifference => "kind_changed",
+            AliasKindChanged { .. } => "alias_kind_changed",
+            StaticMutabilityChanged { .. } => "static_mutability_changed",
+            VarianceLoosened => "variance_loosened",
+            VarianceTightened => "variance_tightened",
+            VarianceChanged { .. } => "variance_changed",
+            RegionParameterAdded => "region_parameter_added",
+            RegionParameterRemoved => "region_parameter_removed",
+            RegionBindingChanged { .. } => "region_binding_changed",
+            TypeParameterAdded { defaulted: false } => "type_parameter_added",
+            TypeParameterAdded { defaulted: true } => "defaulted_type_parameter_added",
+            TypeParameterRemoved { .. } => "type_parameter_removed",
+            VariantAdded => "enum_variant_added",
+            VariantRemoved => "enum_variant_removed",
+            VariantFieldAdded {
+                public: false,
+                total_public: false,
+                ..
+            } => "private_field_added",
+            VariantFieldAdded { .. } => "field_added",
+            VariantFieldRemoved {
+                public: false,
+                is_enum: false,
+                ..
+            } => "private_field_removed",
+            VariantFieldRemoved { .. } => "field_removed",
+            VariantStyleChanged { .. } => "variant_style_changed",
+            ConstructorChanged { .. } => "constructor_changed",
+            TurbofishBroken { .. } => "turbofish_broken",
+            FnConstChanged { .. } => "fn_const_changed",
+            MethodSelfChanged { .. } => "method_self_changed",
+            TraitItemAdded { .. } => "trait_item_added",
+            TraitItemRemoved { .. } => "trait_item_removed",
+            TraitItemMovedToSupertrait { .. } => "trait_item_moved_to_supertrait",
+            TraitItemImplMovedToSupertrait { .. } => "trait_item_impl_moved_to_supertrait",
+            TraitUnsafetyChanged { .. } => "trait_unsafety_changed",
+            TypeChanged { .. } => "type_changed",
+            FnArgCountChanged { .. } => "fn_arg_count_changed",
+            FnArgTypeChanged { .. } => "fn_arg_type_changed",
+            FnReturnTypeChanged { .. } => "fn_return_type_changed",
+            FutureSendChanged { .. } => "future_send_changed",
+            FutureCaptureChanged { .. } => "future_capture_changed",
+            FutureBoundChanged { .. } => "future_bound_changed",
+            BoundsTightened { .. } => "bounds_tightened",
+            BoundsLoosened { .. } => "bounds_loosened",
+            SizedBoundAdded { .. } => "sized_bound_added",
+            SizedBoundRemoved { .. } => "sized_bound_removed",
+            PublicDependencyChanged { .. } => "public_dependency_changed",
+            TraitImplTightened => "trait_impl_tightened",
+            TraitImplLoosened => "trait_impl_loosened",
+            AssociatedItemAdded => "associated_item_added",
+            AssociatedItemRemoved => "associated_item_removed",
+            MethodMovedToTrait { .. } => "method_moved_to_trait",
+            MethodMovedFromTrait { .. } => "method_moved_from_trait",
+            Unknown => "unknown",
+        }
+    }
+
+    /// Check whether the change type only adds to the API of an item.
+    fn is_addition(&self) -> bool {
+        match *self {
+            ItemMadePublic
+            | AssociatedItemAdded
+            | TraitImplLoosened
+            | ConstructorChanged {
+                change: CtorChange::Added,
+            }
+            | ConstructorChanged {
+                change: CtorChange::MadePublic,
+            } => true,
+            TraitItemAdded {
+                defaulted,
+                sealed_trait,
+            } => defaulted || sealed_trait,
+            _ => false,
+        }
+    }
+
     /// Get the change type's category.
     pub fn to_category(&self) -> ChangeCategory {
         // TODO: slightly messy and unreadable.
@@ -377,6 +670,7 @@ impl<'tcx> ChangeType<'tcx> {
             VarianceChanged { .. } |
             RegionParameterAdded |
             RegionParameterRemoved |
+            RegionBindingChanged { now_late_bound: false, .. } |
             TypeParameterAdded { defaulted: false } |
             TypeParameterRemoved { .. } |
             VariantAdded |
@@ -386,29 +680,53 @@ impl<'tcx> ChangeType<'tcx> {
             VariantFieldRemoved { public: true, .. } |
             VariantFieldRemoved { public: false, is_enum: true, .. } |
             VariantStyleChanged { .. } |
+            ConstructorChanged { change: CtorChange::Removed } |
+            ConstructorChanged { change: CtorChange::MadePrivate } |
+            ConstructorChanged { change: CtorChange::ArityChanged { .. } } |
             TypeChanged { .. } |
+            FnArgCountChanged { .. } |
+            FnArgTypeChanged { .. } |
+            FnReturnTypeChanged { .. } |
+            FutureSendChanged { now_send: false } |
+            FutureCaptureChanged { now_captured: true, .. } |
+            FutureBoundChanged { now_bound: false, .. } |
             FnConstChanged { now_const: false } |
             MethodSelfChanged { now_self: false } |
             TraitItemAdded { defaulted: false, sealed_trait: false } |
             TraitItemRemoved { .. } |
+            TraitItemImplMovedToSupertrait { .. } |
             TraitUnsafetyChanged { .. } |
             BoundsTightened { .. } |
             BoundsLoosened { trait_def: true, .. } |
+            SizedBoundAdded { .. } |
+            SizedBoundRemoved { trait_def: true, .. } |
+            PublicDependencyChanged { .. } |
             TraitImplTightened |
             AssociatedItemRemoved |
+            MethodMovedToTrait { .. } |
             Unknown => Breaking,
             MethodSelfChanged { now_self: true } |
             TraitItemAdded { .. } | // either defaulted or sealed
             BoundsLoosened { trait_def: false, .. } |
+            SizedBoundRemoved { trait_def: false, .. } |
             TraitImplLoosened |
             AssociatedItemAdded |
+            ConstructorChanged { .. } | // either added or made public
+            TurbofishBroken { .. } |
+            RegionBindingChanged { now_late_bound: true, .. } |
+            TraitItemMovedToSupertrait { .. } |
             ItemMadePublic => TechnicallyBreaking,
             StaticMutabilityChanged { now_mut: true } |
+            AliasKindChanged { .. } |
             VarianceLoosened |
             TypeParameterAdded { defaulted: true } |
             VariantFieldAdded { public: false, .. } |
             VariantFieldRemoved { public: false, .. } |
-            FnConstChanged { now_const: true } => NonBreaking,
+            FutureSendChanged { now_send: true } |
+            FutureCaptureChanged { now_captured: false, .. } |
+            FutureBoundChanged { now_bound: true, .. } |
+            FnConstChanged { now_const: true } |
+            MethodMovedFromTrait { .. } => NonBreaking,
         }
     }
 
@@ -428,6 +746,11 @@ breaking\"."
                 "Changing the \"kind\" of an item between versions is a breaking change,
 because the usage of the old and new version of the item need not be
 compatible."
+            }
+            AliasKindChanged { .. } => {
+                "Replacing a struct, enum or union with a type alias expanding to an equivalent
+type, or vice-versa, is a non-breaking change by itself. Any actual differences between
+the two types are reported separately."
             }
             StaticMutabilityChanged { now_mut: true } => {
                 "Making a static item mutable is a non-breaking change, because any (old)
@@ -464,6 +787,22 @@ before."
                 "Removing a region parameter is a breaking change, because it can break
 explicit type annotations, as well as prevent region inference working as
 before."
+            }
+            RegionBindingChanged {
+                now_late_bound: false,
+                ..
+            } => {
+                "Making a late-bound lifetime of a function early-bound, for example by
+using it in a where clause, is a breaking change, because the function no
+longer coerces to a higher-ranked function pointer like `for<'a> fn(&'a T)`."
+            }
+            RegionBindingChanged {
+                now_late_bound: true,
+                ..
+            } => {
+                "Making an early-bound lifetime of a function late-bound is a technically
+breaking change, because explicitly specifying it using the turbofish syntax,
+i.e. `foo::<'a>()`, is no longer possible."
             }
             TypeParameterAdded { defaulted: true } => {
                 "Adding a new defaulted type parameter is a non-breaking change, because
@@ -501,6 +840,43 @@ variant are invalidated. In case of structs, this only holds for public fields."
                 "Changing the style of a variant is a breaking change, since most old
 references to it are rendered invalid: pattern matches and value
 construction needs to use the other constructor syntax, respectively."
+            }
+            ConstructorChanged {
+                change: CtorChange::Added,
+            }
+            | ConstructorChanged {
+                change: CtorChange::MadePublic,
+            } => {
+                "Making a struct or variant constructible by name is a breaking change in
+some specific situations: The constructor lives in the value namespace, where
+it can cause nameclashes with items imported through wildcard imports in user
+code. Thus, the change is classified as \"technically breaking\"."
+            }
+            ConstructorChanged {
+                change: CtorChange::Removed,
+            }
+            | ConstructorChanged {
+                change: CtorChange::MadePrivate,
+            } => {
+                "Removing a struct's or variant's constructor, or making it private (for
+example by adding a private field or `#[non_exhaustive]`), is a breaking
+change, because old code constructing the item or matching on it using the
+constructor is rendered invalid."
+            }
+            ConstructorChanged {
+                change: CtorChange::ArityChanged { .. },
+            } => {
+                "Changing the number of arguments of a public tuple struct's or variant's
+constructor is a breaking change, because all old invocations of the
+constructor and patterns using it are rendered invalid."
+            }
+            TurbofishBroken { .. } => {
+                "Changing the explicit generic parameters of a function is breaking for code
+that specifies them explicitly using the turbofish syntax, i.e. `foo::<T>()`. This
+applies to reordering them, changing their number, and adding `impl Trait`
+arguments, since explicit generic arguments can't be provided to functions taking
+those. Since most calls rely on type inference instead, the change is classified as
+\"technically breaking\"."
             }
             FnConstChanged { now_const: true } => {
                 "Making a function const is a non-breaking change, because a const function
@@ -546,6 +922,18 @@ implementations of the trait in user code become invalid."
             TraitItemRemoved { .. } => {
                 "Removing a trait item is a breaking change, because all old references
 to the item become invalid."
+            }
+            TraitItemMovedToSupertrait { .. } => {
+                "Moving a trait item to a supertrait keeps it available wherever the trait is
+used as a bound or trait object. However, calling the item as a method on a concrete
+type now requires the supertrait to be in scope, so code only importing the trait
+itself can break. Thus, the change is classified as \"technically breaking\". The
+impact on implementors is reported separately."
+            }
+            TraitItemImplMovedToSupertrait { .. } => {
+                "Moving a trait item to a supertrait is a breaking change for implementors,
+because the item now has to be implemented as part of the supertrait. Since user
+code can't implement sealed traits, this isn't reported for them."
             }
             TraitUnsafetyChanged { .. } => {
                 "Changing the unsafety of a trait is a breaking change, because all
@@ -554,6 +942,59 @@ implementations become invalid."
             TypeChanged { .. } => {
                 "Changing the type of an item is a breaking change, because user code
 using the item becomes type-incorrect."
+            }
+            FnArgCountChanged { .. } => {
+                "Adding or removing a function parameter is a breaking change, because all
+old calls to the function pass the wrong number of arguments."
+            }
+            FnArgTypeChanged { .. } => {
+                "Changing the type of a function parameter is a breaking change, because old
+calls to the function can pass arguments of the wrong type."
+            }
+            FnReturnTypeChanged { .. } => {
+                "Changing the return type of a function is a breaking change, because user
+code using the returned value can become type-incorrect."
+            }
+            FutureSendChanged { now_send: false } => {
+                "Making the future returned by a function lose its `Send` implementation,
+for example by holding a non-`Send` value across an `.await`, is a breaking
+change, because user code spawning the future on a multithreaded executor
+is rendered invalid."
+            }
+            FutureSendChanged { now_send: true } => {
+                "Making the future returned by a function implement `Send` is a
+non-breaking change, because it can be used anywhere it could be used
+before."
+            }
+            FutureCaptureChanged {
+                now_captured: true, ..
+            } => {
+                "Making the future returned by a function capture the lifetime of a
+parameter is a breaking change, because user code keeping the future around
+after the argument has gone out of scope is rendered invalid."
+            }
+            FutureCaptureChanged {
+                now_captured: false,
+                ..
+            } => {
+                "Making the future returned by a function stop capturing the lifetime
+of a parameter is a non-breaking change, because the future can be used
+anywhere it could be used before."
+            }
+            FutureBoundChanged {
+                now_bound: false, ..
+            } => {
+                "Making the future returned by a function stop implementing a trait, for
+example by removing `+ Unpin` from its `impl Future` return type, is a
+breaking change, because user code relying on the trait implementation, like
+polling the future without pinning it, is rendered invalid."
+            }
+            FutureBoundChanged {
+                now_bound: true, ..
+            } => {
+                "Making the future returned by a function implement another trait is a
+non-breaking change, because it can be used anywhere it could be used
+before."
             }
             BoundsTightened { .. } => {
                 "Tightening the bounds of a lifetime or type parameter is a breaking
@@ -574,6 +1015,33 @@ trait implementation or usage."
                 "Loosening the bounds of a lifetime or type parameter in a non-trait
 definition is a non-breaking change, because all old references to the
 item would remain valid."
+            }
+            SizedBoundAdded { .. } => {
+                "Removing `?Sized` from a type parameter is a breaking change, because the
+parameter can no longer be instantiated with dynamically sized types like
+`str`, `[T]` or trait objects, rendering such old references invalid."
+            }
+            SizedBoundRemoved {
+                trait_def: true, ..
+            } => {
+                "Adding `?Sized` to a type parameter of a trait definition is a breaking
+change, because implementors and users of the trait can no longer assume
+that the parameter is `Sized`, which invalidates code moving or storing
+values of its type."
+            }
+            SizedBoundRemoved {
+                trait_def: false, ..
+            } => {
+                "Adding `?Sized` to a type parameter of a non-trait definition is a
+non-breaking change, because all old references to the item remain valid
+and can now additionally use dynamically sized types."
+            }
+            PublicDependencyChanged { .. } => {
+                "Switching to a different version of a dependency whose types or traits
+appear in an item's public signature is a breaking change, even if the
+signature looks identical, because the items of different versions of a
+crate are distinct. User code passing values obtained from their own copy
+of the dependency is rendered invalid."
             }
             TraitImplTightened => {
                 "Effectively removing a trait implementation for a (possibly
@@ -595,6 +1063,17 @@ breaking\"."
             AssociatedItemRemoved => {
                 "Removing an item from an inherent impl is a breaking change, as all old
 references to it become invalid."
+            }
+            MethodMovedToTrait { .. } => {
+                "Moving a method from an inherent impl to a trait impl is a breaking change,
+as the method can only be called if the trait is in scope. User code needs to
+import the trait to keep compiling."
+            }
+            MethodMovedFromTrait { .. } => {
+                "Adding an inherent method with the name of a method from a trait impl that has
+been removed is a non-breaking change by itself. Method call syntax keeps working,
+but the trait import may become unused. All old references to the method through
+the trait become invalid, which is reported as part of the removal of the trait impl."
             }
             Unknown => "No explanation for unknown changes.",
         }
@@ -607,6 +1086,8 @@ impl<'a> fmt::Display for ChangeType<'a> {
             ItemMadePublic => "item made public",
             ItemMadePrivate => "item made private",
             KindDifference => "item kind changed",
+            AliasKindChanged { now_alias: true } => "item replaced by a type alias",
+            AliasKindChanged { now_alias: false } => "type alias replaced by a nominal type",
             StaticMutabilityChanged { now_mut: true } => "static item made mutable",
             StaticMutabilityChanged { now_mut: false } => "static item made immutable",
             VarianceLoosened => "variance loosened",
@@ -619,6 +1100,22 @@ impl<'a> fmt::Display for ChangeType<'a> {
             } => "variance changed from contra- to covariant",
             RegionParameterAdded => "region parameter added",
             RegionParameterRemoved => "region parameter removed",
+            RegionBindingChanged {
+                name,
+                now_late_bound,
+            } => {
+                let (from, to) = if now_late_bound {
+                    ("early", "late")
+                } else {
+                    ("late", "early")
+                };
+
+                return write!(
+                    f,
+                    "lifetime parameter `{}` changed from {}-bound to {}-bound",
+                    name, from, to
+                );
+            }
             TypeParameterAdded { defaulted: true } => "defaulted type parameter added",
             TypeParameterAdded { defaulted: false } => "type parameter added",
             TypeParameterRemoved { defaulted: true } => "defaulted type parameter removed",
@@ -745,8 +1242,39 @@ impl<'a> fmt::Display for ChangeType<'a> {
                 total_private: false,
                 is_enum: false,
             } => "struct with public fields changed to a tuple struct",
+            TurbofishBroken {
+                change: TurbofishChange::Reordered,
+            } => "generic parameters reordered, breaking turbofish calls",
+            TurbofishBroken {
+                change: TurbofishChange::ImplTraitIntroduced,
+            } => "`impl Trait` argument introduced, breaking turbofish calls",
+            TurbofishBroken {
+                change: TurbofishChange::ArityChanged { old, new },
+            } => {
+                return write!(
+                    f,
+                    "number of explicit generic parameters changed from {} to {}, \
+                     breaking turbofish calls",
+                    old, new
+                )
+            }
             FnConstChanged { now_const: true } => "fn item made const",
             FnConstChanged { now_const: false } => "fn item made non-const",
+            ConstructorChanged {
+                change: CtorChange::Added,
+            } => "public constructor added",
+            ConstructorChanged {
+                change: CtorChange::Removed,
+            } => "public constructor removed",
+            ConstructorChanged {
+                change: CtorChange::MadePublic,
+            } => "constructor made public",
+            ConstructorChanged {
+                change: CtorChange::MadePrivate,
+            } => "constructor made private",
+            ConstructorChanged {
+                change: CtorChange::ArityChanged { old, new },
+            } => return write!(f, "constructor arguments changed from {} to {}", old, new),
             MethodSelfChanged { now_self: true } => "added self-argument to method",
             MethodSelfChanged { now_self: false } => "removed self-argument from method",
             TraitItemAdded {
@@ -759,9 +1287,64 @@ impl<'a> fmt::Display for ChangeType<'a> {
             TraitItemAdded { .. } => "added item to trait",
             TraitItemRemoved { defaulted: true } => "removed defaulted item from trait",
             TraitItemRemoved { defaulted: false } => "removed item from trait",
+            TraitItemMovedToSupertrait { ref supertrait } => {
+                return write!(f, "item moved to supertrait `{}`", supertrait)
+            }
+            TraitItemImplMovedToSupertrait { ref supertrait } => {
+                return write!(
+                    f,
+                    "item has to be implemented as part of supertrait `{}`",
+                    supertrait
+                )
+            }
             TraitUnsafetyChanged { now_unsafe: true } => "trait made unsafe",
             TraitUnsafetyChanged { now_unsafe: false } => "trait no longer unsafe",
             TypeChanged { ref error } => return write!(f, "type error: {}", error),
+            FnArgCountChanged { old, new } => {
+                return write!(f, "number of parameters changed from {} to {}", old, new)
+            }
+            FnArgTypeChanged { index, old, new } => {
+                return write!(
+                    f,
+                    "type of parameter {} changed from `{}` to `{}`",
+                    index + 1,
+                    old,
+                    new
+                )
+            }
+            FnReturnTypeChanged { old, new } => {
+                return write!(f, "return type changed from `{}` to `{}`", old, new)
+            }
+            FutureSendChanged { now_send: true } => "returned future is now `Send`",
+            FutureSendChanged { now_send: false } => "returned future is no longer `Send`",
+            FutureCaptureChanged {
+                param,
+                now_captured,
+            } => {
+                if now_captured {
+                    return write!(
+                        f,
+                        "returned future now captures the lifetime of `{}`",
+                        param
+                    );
+                } else {
+                    return write!(
+                        f,
+                        "returned future no longer captures the lifetime of `{}`",
+                        param
+                    );
+                }
+            }
+            FutureBoundChanged {
+                ref bound,
+                now_bound,
+            } => {
+                if now_bound {
+                    return write!(f, "returned future now implements `{}`", bound);
+                } else {
+                    return write!(f, "returned future no longer implements `{}`", bound);
+                }
+            }
             BoundsTightened { ref pred } => return write!(f, "added bound: `{}`", pred),
             BoundsLoosened {
                 ref pred,
@@ -773,10 +1356,48 @@ impl<'a> fmt::Display for ChangeType<'a> {
                     return write!(f, "removed bound: `{}`", pred);
                 }
             }
+            SizedBoundAdded { param } => {
+                return write!(f, "`?Sized` removed from type parameter `{}`", param)
+            }
+            SizedBoundRemoved { param, trait_def } => {
+                if trait_def {
+                    return write!(
+                        f,
+                        "`?Sized` added to type parameter `{}` of trait definition",
+                        param
+                    );
+                } else {
+                    return write!(f, "`?Sized` added to type parameter `{}`", param);
+                }
+            }
+            PublicDependencyChanged {
+                krate,
+                old_version: Some(ref old_version),
+                new_version: Some(ref new_version),
+            } => {
+                return write!(
+                    f,
+                    "public dependency `{}` changed from version {} to {}",
+                    krate, old_version, new_version
+                )
+            }
+            PublicDependencyChanged { krate, .. } => {
+                return write!(
+                    f,
+                    "public dependency `{}` changed to a different version",
+                    krate
+                )
+            }
             TraitImplTightened => "trait impl specialized or removed",
             TraitImplLoosened => "trait impl generalized or newly added",
             AssociatedItemAdded => "added item in inherent impl",
             AssociatedItemRemoved => "removed item in inherent impl",
+            MethodMovedToTrait { ref trait_path } => {
+                return write!(f, "method moved to impl of trait `{}`", trait_path)
+            }
+            MethodMovedFromTrait { ref trait_path } => {
+                return write!(f, "method moved out of impl of trait `{}`", trait_path)
+            }
             Unknown => "unknown change",
         };
         write!(f, "{}", desc)
@@ -792,6 +1413,234 @@ impl<'tcx> Serialize for ChangeType<'tcx> {
     }
 }
 
+/// An acknowledgement of changes to an item, given by a `semverver::allow` attribute.
+#[derive(Clone, Debug, Serialize)]
+pub struct Allowance {
+    /// The most severe category of changes allowed.
+    pub category: ChangeCategory,
+    /// The reason given for allowing the changes, if any.
+    pub reason: Option<String>,
+}
+
+/// A change acknowledged in a baseline file.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    /// A stable identifier, derived from the item path and the change description.
+    pub id: String,
+    /// The path of the affected item.
+    pub path: String,
+    /// The description of the change.
+    pub change: String,
+    /// The category of the change.
+    pub category: ChangeCategory,
+}
+
+impl BaselineEntry {
+    /// Construct a new baseline entry, computing its identifier.
+    fn new(path: String, change: String, category: ChangeCategory) -> Self {
+        Self {
+            id: baseline_id(&path, &change),
+            path,
+            change,
+            category,
+        }
+    }
+}
+
+/// Compute the identifier of a baseline entry.
+///
+/// This is a FNV-1a hash, which doesn't depend on the platform or compiler version used.
+fn baseline_id(path: &str, change: &str) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+
+    for byte in path.bytes().chain(Some(0)).chain(change.bytes()) {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+
+    format!("{:016x}", hash)
+}
+
+/// The names of all lints, i.e. those of the change types and path changes.
+///
+/// NB: This needs to be kept in sync with `ChangeType::lint_name`.
+pub const LINTS: &[&str] = &[
+    "path_added",
+    "path_removed",
+    "item_made_public",
+    "item_made_private",
+    "kind_changed",
+    "alias_kind_changed",
+    "static_mutability_changed",
+    "variance_loosened",
+    "variance_tightened",
+    "variance_changed",
+    "region_parameter_added",
+    "region_parameter_removed",
+    "region_binding_changed",
+    "type_parameter_added",
+    "defaulted_type_parameter_added",
+    "type_parameter_removed",
+    "enum_variant_added",
+    "enum_variant_removed",
+    "field_added",
+    "private_field_added",
+    "field_removed",
+    "private_field_removed",
+    "variant_style_changed",
+    "constructor_changed",
+    "turbofish_broken",
+    "fn_const_changed",
+    "method_self_changed",
+    "trait_item_added",
+    "trait_item_removed",
+    "trait_item_moved_to_supertrait",
+    "trait_item_impl_moved_to_supertrait",
+    "trait_unsafety_changed",
+    "type_changed",
+    "fn_arg_count_changed",
+    "fn_arg_type_changed",
+    "fn_return_type_changed",
+    "future_send_changed",
+    "future_capture_changed",
+    "future_bound_changed",
+    "bounds_tightened",
+    "bounds_loosened",
+    "sized_bound_added",
+    "sized_bound_removed",
+    "public_dependency_changed",
+    "trait_impl_tightened",
+    "trait_impl_loosened",
+    "associated_item_added",
+    "associated_item_removed",
+    "method_moved_to_trait",
+    "method_moved_from_trait",
+    "unknown",
+];
+
+/// The policy profiles determining the categories of changes and which of them get reported.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Policy {
+    /// Categorize changes as laid out in RFC 1105.
+    Rfc1105,
+    /// Treat technically breaking changes as breaking.
+    Strict,
+    /// Categorize changes as laid out in RFC 1105, but only report breaking changes.
+    ApiGuidelines,
+    /// Ignore additions to the API.
+    Lenient,
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Policy::Rfc1105
+    }
+}
+
+impl Policy {
+    /// Parse the name of a policy profile.
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "rfc1105" => Ok(Policy::Rfc1105),
+            "strict" => Ok(Policy::Strict),
+            "api-guidelines" => Ok(Policy::ApiGuidelines),
+            "lenient" => Ok(Policy::Lenient),
+            _ => Err(format!("unknown policy `{}`", name)),
+        }
+    }
+
+    /// Adjust the category of a change according to the policy.
+    fn adjust(self, cat: ChangeCategory, addition: bool) -> ChangeCategory {
+        match self {
+            Policy::Strict if cat == TechnicallyBreaking => Breaking,
+            Policy::Lenient if addition => Patch,
+            _ => cat,
+        }
+    }
+
+    /// Get the category of a change type.
+    fn category(self, type_: &ChangeType) -> ChangeCategory {
+        self.adjust(type_.to_category(), type_.is_addition())
+    }
+
+    /// Get the category of an added or removed export of an item.
+    fn path_category(self, add: bool) -> ChangeCategory {
+        if add {
+            self.adjust(TechnicallyBreaking, true)
+        } else {
+            self.adjust(Breaking, false)
+        }
+    }
+
+    /// Check whether changes to an item of the given category get reported.
+    fn reports(self, cat: ChangeCategory) -> bool {
+        match self {
+            Policy::ApiGuidelines => cat == Patch || cat == Breaking,
+            _ => true,
+        }
+    }
+}
+
+/// Lint levels overriding the categories of changes determined by a policy profile.
+///
+/// Allowed changes are treated as patch-level changes, and thus not reported at all, while
+/// warnings are treated as technically breaking and denied changes as breaking.
+#[derive(Clone, Debug, Default)]
+pub struct LintLevels {
+    /// The policy profile determining the categories of lints without a level.
+    policy: Policy,
+    /// The categories of the lints given a level.
+    levels: HashMap<String, ChangeCategory>,
+}
+
+impl LintLevels {
+    /// Parse a comma-separated list of `lint=level` pairs, with later pairs taking precedence.
+    ///
+    /// Lint names may be given with or without the `semver::` prefix.
+    pub fn parse(policy: Policy, list: &str) -> Result<Self, String> {
+        let mut levels = HashMap::new();
+
+        for pair in list.split(',').filter(|pair| !pair.is_empty()) {
+            let (name, level) = pair
+                .split_once('=')
+                .ok_or_else(|| format!("malformed lint level `{}`", pair))?;
+            let name = name.trim();
+            let name = name.strip_prefix("semver::").unwrap_or(name);
+
+            if !LINTS.contains(&name) {
+                return Err(format!("unknown lint `semver::{}`", name));
+            }
+
+            let cat = match level.trim() {
+                "allow" => Patch,
+                "warn" => TechnicallyBreaking,
+                "deny" => Breaking,
+                level => return Err(format!("unknown lint level `{}`", level)),
+            };
+
+            levels.insert(name.to_owned(), cat);
+        }
+
+        Ok(Self { policy, levels })
+    }
+
+    /// Get the category of changes reported by the given lint.
+    fn get(&self, name: &str, default: ChangeCategory) -> ChangeCategory {
+        self.levels.get(name).copied().unwrap_or(default)
+    }
+
+    /// Get the category of a change type.
+    pub fn category(&self, type_: &ChangeType) -> ChangeCategory {
+        self.get(type_.lint_name(), self.policy.category(type_))
+    }
+
+    /// Get the category of an added or removed export of an item.
+    fn path_category(&self, add: bool) -> ChangeCategory {
+        let name = if add { "path_added" } else { "path_removed" };
+        self.get(name, self.policy.path_category(add))
+    }
+}
+
 /// A change record of an item present in both crate versions.
 ///
 /// NB: `Eq` and `Ord` instances are constucted to only regard the *new* span of the associated
@@ -805,25 +1654,67 @@ pub struct Change<'tcx> {
     name: Name,
     /// The new definition span of the item.
     new_span: Span,
+    /// The old definition span of the item, if it is present in both crate versions.
+    old_span: Option<Span>,
     /// Whether to output changes. Used to distinguish all-private items.
     output: bool,
+    /// The dependency crate the item has been re-exported from, if any.
+    upstream_crate: Option<Symbol>,
+    /// The changes acknowledged in the new crate, if any.
+    allowance: Option<Allowance>,
+    /// The lint levels determining the categories of the changes.
+    lints: Rc<LintLevels>,
 }
 
 impl<'tcx> Change<'tcx> {
     /// Construct a new empty change record for an item.
-    fn new(name: Name, span: Span, output: bool) -> Change<'tcx> {
+    fn new(name: Name, span: Span, output: bool, lints: Rc<LintLevels>) -> Change<'tcx> {
         Change {
             changes: Vec::new(),
             max: ChangeCategory::default(),
             name,
             new_span: span,
+            old_span: None,
             output,
+            upstream_crate: None,
+            allowance: None,
+            lints,
         }
     }
 
+    /// Get the category of a change type, as determined by the lint levels.
+    fn category(&self, type_: &ChangeType) -> ChangeCategory {
+        self.lints.category(type_)
+    }
+
+    /// Acknowledge the changes up to a given category, which are then excluded from the most
+    /// severe change category of the item.
+    fn allow(&mut self, allowance: Allowance) {
+        self.allowance = Some(allowance);
+        self.update_max();
+    }
+
+    /// Recompute the most severe category of the changes that haven't been acknowledged.
+    fn update_max(&mut self) {
+        self.max = self
+            .changes
+            .iter()
+            .map(|change| self.category(&change.0))
+            .filter(|cat| !self.is_allowed(*cat))
+            .max()
+            .unwrap_or_default();
+    }
+
+    /// Check whether changes of the given category have been acknowledged.
+    fn is_allowed(&self, cat: ChangeCategory) -> bool {
+        self.allowance
+            .as_ref()
+            .map_or(false, |allowance| cat <= allowance.category)
+    }
+
     /// Insert another change type into an existing path change record.
     fn insert(&mut self, type_: ChangeType<'tcx>, span: Option<Span>) {
-        let cat = type_.to_category();
+        let cat = self.category(&type_);
 
         if cat > self.max {
             self.max = cat;
@@ -848,15 +1739,23 @@ impl<'tcx> Change<'tcx> {
                 | VariantFieldAdded { .. }
                 | VariantFieldRemoved { .. }
                 | VariantStyleChanged { .. }
+                | ConstructorChanged { .. }
                 | TypeChanged { .. }
+                | FnArgCountChanged { .. }
+                | FnArgTypeChanged { .. }
+                | FnReturnTypeChanged { .. }
                 | FnConstChanged { now_const: false }
                 | MethodSelfChanged { now_self: false }
                 | Unknown => return true,
                 StaticMutabilityChanged { .. }
+                | AliasKindChanged { .. }
                 | RegionParameterAdded
+                | RegionBindingChanged { .. }
                 | MethodSelfChanged { now_self: true }
                 | TraitItemAdded { .. }
                 | TraitItemRemoved { .. }
+                | TraitItemMovedToSupertrait { .. }
+                | TraitItemImplMovedToSupertrait { .. }
                 | ItemMadePublic
                 | VarianceLoosened
                 | VarianceTightened
@@ -864,12 +1763,21 @@ impl<'tcx> Change<'tcx> {
                 | TypeParameterAdded { .. }
                 | TraitUnsafetyChanged { .. }
                 | FnConstChanged { now_const: true }
+                | TurbofishBroken { .. }
+                | FutureSendChanged { .. }
+                | FutureCaptureChanged { .. }
+                | FutureBoundChanged { .. }
                 | BoundsTightened { .. }
                 | BoundsLoosened { .. }
+                | SizedBoundAdded { .. }
+                | SizedBoundRemoved { .. }
+                | PublicDependencyChanged { .. }
                 | TraitImplTightened
                 | TraitImplLoosened
                 | AssociatedItemAdded
-                | AssociatedItemRemoved => (),
+                | AssociatedItemRemoved
+                | MethodMovedToTrait { .. }
+                | MethodMovedFromTrait { .. } => (),
             }
         }
 
@@ -887,33 +1795,87 @@ impl<'tcx> Change<'tcx> {
     }
 
     /// Report the change in a structured manner, using rustc's error reporting capabilities.
+    ///
+    /// Acknowledged changes are only listed in verbose mode.
     fn report(&self, session: &Session, verbose: bool) {
-        if self.max == Patch || !self.output {
+        let allowed = verbose
+            && self
+                .changes
+                .iter()
+                .map(|c| self.category(&c.0))
+                .any(|cat| cat > Patch && self.is_allowed(cat));
+
+        if (self.max == Patch && !allowed) || !self.output {
+            return;
+        }
+
+        let msg = if self.max == Patch {
+            format!("allowed changes in {}", self.name)
+        } else if let Some(upstream_crate) = self.upstream_crate {
+            format!(
+                "{} changes in {} (re-exported from `{}`)",
+                self.max, self.name, upstream_crate
+            )
+        } else {
+            format!("{} changes in {}", self.max, self.name)
+        };
+
+        self.emit(session, &msg, verbose, Patch);
+    }
+
+    /// Report the breaking changes as incompatibilities of a replacement crate.
+    fn report_incompatible(&self, session: &Session, verbose: bool) {
+        if self.max != Breaking || !self.output {
             return;
         }
 
-        let msg = format!("{} changes in {}", self.max, self.name);
+        let msg = format!("{} is incompatible in the replacement", self.name);
+        self.emit(session, &msg, verbose, Breaking);
+    }
+
+    /// Emit a diagnostic with the given message, listing all changes of at least the given
+    /// category.
+    fn emit(&self, session: &Session, msg: &str, verbose: bool, min_category: ChangeCategory) {
         let mut builder = if self.max == Breaking {
-            session.struct_span_err(self.new_span, &msg)
+            session.struct_span_err(self.new_span, msg)
         } else {
-            session.struct_span_warn(self.new_span, &msg)
+            session.struct_span_warn(self.new_span, msg)
         };
 
         for change in &self.changes {
-            let cat = change.0.to_category();
-            let sub_msg = if verbose {
-                format!("{} ({}):\n{}", change.0, cat, change.0.explanation())
-            } else {
-                format!("{} ({})", change.0, cat)
+            let cat = self.category(&change.0);
+            if cat < min_category || cat == Patch {
+                continue;
+            }
+
+            let allowed = self.is_allowed(cat);
+            if allowed && !verbose {
+                continue;
+            }
+
+            let sub_msg = match self.allowance {
+                Some(Allowance {
+                    reason: Some(ref reason),
+                    ..
+                }) if allowed => format!("{} ({}, allowed: {})", change.0, cat, reason),
+                _ if allowed => format!("{} ({}, allowed)", change.0, cat),
+                _ if verbose => format!(
+                    "{} ({}, semver::{}):\n{}",
+                    change.0,
+                    cat,
+                    change.0.lint_name(),
+                    change.0.explanation()
+                ),
+                _ => format!("{} ({})", change.0, cat),
             };
 
             if let Some(span) = change.1 {
-                if cat == Breaking {
+                if cat == Breaking && !allowed {
                     builder.span_warn(span, &sub_msg);
                 } else {
                     builder.span_note(span, &sub_msg);
                 }
-            } else if cat == Breaking {
+            } else if cat == Breaking && !allowed {
                 // change.1 == None from here on.
                 builder.warn(&sub_msg);
             } else {
@@ -952,15 +1914,21 @@ impl<'a, 'tcx> Serialize for RChange<'a, 'tcx> {
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("Change", 4)?;
+        let mut state = serializer.serialize_struct("Change", 6)?;
         state.serialize_field("name", &self.1.name)?;
+        state.serialize_field(
+            "upstream_crate",
+            &self.1.upstream_crate.map(|krate| krate.to_string()),
+        )?;
         state.serialize_field("max_category", &self.1.max)?;
+        state.serialize_field("allowed", &self.1.allowance)?;
         state.serialize_field("new_span", &RSpan(self.0, &self.1.new_span))?;
 
         let changes: Vec<_> = self
             .1
             .changes
             .iter()
+            .filter(|(t, _)| self.1.category(t) > Patch)
             .map(|(t, s)| (t, s.as_ref().map(|s| RSpan(self.0, s))))
             .collect();
 
@@ -969,6 +1937,108 @@ impl<'a, 'tcx> Serialize for RChange<'a, 'tcx> {
     }
 }
 
+/// The components of a version number.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum VersionComponent {
+    Major,
+    Minor,
+    Patch,
+}
+
+impl fmt::Display for VersionComponent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let desc = match *self {
+            VersionComponent::Major => "major",
+            VersionComponent::Minor => "minor",
+            VersionComponent::Patch => "patch",
+        };
+
+        write!(f, "{}", desc)
+    }
+}
+
+/// Compute the version to follow the given one, given the most severe category of changes.
+///
+/// This follows the semantics of cargo's caret requirements, which consider the leftmost
+/// nonzero component of a version number to denote compatibility. Pre-release versions are
+/// succeeded by the next pre-release of the same version if the changes allow it, and build
+/// metadata is dropped. Returns the new version, along with notes explaining the decision
+/// where it doesn't follow from the category of changes alone.
+fn next_version(mut version: Version, max: ChangeCategory) -> (String, Vec<String>) {
+    let mut notes = Vec::new();
+
+    // the component whose increment is breaking in cargo's view
+    let breaking = if version.major > 0 {
+        VersionComponent::Major
+    } else if version.minor > 0 {
+        if max > Patch {
+            notes.push(
+                "for versions 0.x.y, cargo considers a new minor version breaking and a new \
+                 patch version compatible"
+                    .to_owned(),
+            );
+        }
+
+        VersionComponent::Minor
+    } else {
+        notes.push("for versions 0.0.z, cargo considers every new version breaking".to_owned());
+        VersionComponent::Patch
+    };
+
+    let component = match max {
+        Breaking => breaking,
+        NonBreaking | TechnicallyBreaking if breaking == VersionComponent::Major => {
+            VersionComponent::Minor
+        }
+        _ => VersionComponent::Patch,
+    };
+
+    if !version.build.is_empty() {
+        notes.push("build metadata is dropped, as it doesn't distinguish versions".to_owned());
+        version.build.clear();
+    }
+
+    if version.is_prerelease() {
+        // a pre-release precedes a release which increments its rightmost nonzero component
+        let pending = if version.minor == 0 && version.patch == 0 {
+            VersionComponent::Major
+        } else if version.patch == 0 {
+            VersionComponent::Minor
+        } else {
+            VersionComponent::Patch
+        };
+
+        if pending <= component {
+            match version.pre.last_mut() {
+                Some(Identifier::Numeric(n)) => *n += 1,
+                _ => version.pre.push(Identifier::Numeric(1)),
+            }
+
+            notes.push(format!(
+                "the pre-release precedes a new {} version, which can contain the changes, \
+                 so the next pre-release is suggested",
+                pending
+            ));
+
+            return (version.to_string(), notes);
+        }
+
+        notes.push(format!(
+            "the pre-release precedes a new {} version, but the changes require a new {} \
+             version",
+            pending, component
+        ));
+    }
+
+    match component {
+        VersionComponent::Major => version.increment_major(),
+        VersionComponent::Minor => version.increment_minor(),
+        VersionComponent::Patch => version.increment_patch(),
+    }
+
+    (version.to_string(), notes)
+}
+
 /// The total set of changes recorded for two crate versions.
 #[derive(Default)]
 pub struct ChangeSet<'tcx> {
@@ -980,15 +2050,24 @@ pub struct ChangeSet<'tcx> {
     spans: BTreeMap<Span, DefId>,
     /// The most severe change category already recorded.
     max: ChangeCategory,
+    /// The lint levels determining the categories of changes.
+    lints: Rc<LintLevels>,
 }
 
 impl<'tcx> ChangeSet<'tcx> {
+    /// Set the lint levels to determine the categories of changes recorded from now on.
+    pub fn set_lint_levels(&mut self, lints: LintLevels) {
+        self.lints = Rc::new(lints);
+    }
+
     /// Add a new path change entry for the given item.
     pub fn new_path_change(&mut self, old: DefId, name: Symbol, def_span: Span) {
+        let lints = &self.lints;
+
         self.spans.entry(def_span).or_insert_with(|| old);
         self.path_changes
             .entry(old)
-            .or_insert_with(|| PathChange::new(name, def_span));
+            .or_insert_with(|| PathChange::new(name, def_span, lints.clone()));
     }
 
     /// Add a new path addition to an already existing entry.
@@ -1003,13 +2082,14 @@ impl<'tcx> ChangeSet<'tcx> {
 
     /// Add a new path change to an already existing entry.
     fn add_path(&mut self, old: DefId, span: Span, add: bool) {
-        let cat = if add { TechnicallyBreaking } else { Breaking };
+        let change = self.path_changes.get_mut(&old).unwrap();
+        let cat = change.category(add);
 
         if cat > self.max {
             self.max = cat;
         }
 
-        self.path_changes.get_mut(&old).unwrap().insert(span, add);
+        change.insert(span, add);
     }
 
     /// Add a new change entry for the given item pair.
@@ -1022,7 +2102,8 @@ impl<'tcx> ChangeSet<'tcx> {
         new_span: Span,
         output: bool,
     ) {
-        let change = Change::new(Name::symbol(name), new_span, output);
+        let mut change = Change::new(Name::symbol(name), new_span, output, self.lints.clone());
+        change.old_span = Some(old_span);
 
         self.spans.insert(old_span, old_def_id);
         self.spans.insert(new_span, new_def_id);
@@ -1031,7 +2112,7 @@ impl<'tcx> ChangeSet<'tcx> {
 
     /// Add a new change entry for the given trait impl.
     pub fn new_change_impl(&mut self, def_id: DefId, desc: String, span: Span) {
-        let change = Change::new(Name::ImplDesc(desc), span, true);
+        let change = Change::new(Name::ImplDesc(desc), span, true, self.lints.clone());
 
         self.spans.insert(span, def_id);
         self.changes.insert(def_id, change);
@@ -1039,7 +2120,7 @@ impl<'tcx> ChangeSet<'tcx> {
 
     /// Add a new change to an already existing entry.
     pub fn add_change(&mut self, type_: ChangeType<'tcx>, old: DefId, span: Option<Span>) {
-        let cat = type_.to_category();
+        let cat = self.lints.category(&type_);
 
         if cat > self.max && self.get_output(old) {
             self.max = cat;
@@ -1048,6 +2129,158 @@ impl<'tcx> ChangeSet<'tcx> {
         self.changes.get_mut(&old).unwrap().insert(type_, span);
     }
 
+    /// Attribute the changes to items of a dependency crate to that crate.
+    pub fn set_upstream_crate(&mut self, krate: CrateNum, name: Symbol) {
+        for (def_id, change) in &mut self.changes {
+            if def_id.krate == krate {
+                change.upstream_crate = Some(name);
+            }
+        }
+    }
+
+    /// Iterate over the `DefId`s of all items with a change record.
+    pub fn change_ids(&self) -> impl Iterator<Item = DefId> + '_ {
+        self.changes.keys().copied()
+    }
+
+    /// Acknowledge the changes to the given items, each up to a given category.
+    ///
+    /// Acknowledged changes don't contribute to the most severe change category recorded, which
+    /// is recomputed once all allowances have been applied.
+    pub fn allow_changes<I>(&mut self, allowances: I)
+    where
+        I: IntoIterator<Item = (DefId, Allowance)>,
+    {
+        for (old, allowance) in allowances {
+            if let Some(change) = self.changes.get_mut(&old) {
+                change.allow(allowance);
+            }
+        }
+
+        self.update_max();
+    }
+
+    /// Describe all reported changes as baseline entries.
+    pub fn baseline(&self, tcx: TyCtxt) -> Vec<BaselineEntry> {
+        let mut entries = Vec::new();
+        let mut seen = HashSet::new();
+
+        for &key in self.spans.values() {
+            if !seen.insert(key) {
+                continue;
+            }
+
+            if let Some(change) = self.path_changes.get(&key) {
+                let path = tcx.def_path_str(key);
+
+                for (spans, add) in &[(&change.removals, false), (&change.additions, true)] {
+                    let cat = change.category(*add);
+                    if cat == Patch {
+                        continue;
+                    }
+
+                    for span in spans.iter() {
+                        let desc = change.describe(*span, *add);
+                        entries.push(BaselineEntry::new(path.clone(), desc.to_owned(), cat));
+                    }
+                }
+            }
+
+            if let Some(change) = self.changes.get(&key).filter(|change| change.output) {
+                let path = tcx.def_path_str(key);
+
+                for (type_, _) in &change.changes {
+                    let cat = change.category(type_);
+
+                    if cat != Patch && !change.is_allowed(cat) {
+                        entries.push(BaselineEntry::new(path.clone(), type_.to_string(), cat));
+                    }
+                }
+            }
+        }
+
+        let mut ids = HashSet::new();
+        entries.retain(|entry| ids.insert(entry.id.clone()));
+        entries
+    }
+
+    /// Remove the changes described by the given baseline entries.
+    ///
+    /// Returns the entries that don't correspond to any change (anymore).
+    pub fn apply_baseline(
+        &mut self,
+        tcx: TyCtxt,
+        baseline: &[BaselineEntry],
+    ) -> Vec<BaselineEntry> {
+        let ids: HashSet<_> = baseline.iter().map(|entry| entry.id.as_str()).collect();
+        let mut found = HashSet::new();
+
+        for (&def_id, change) in &mut self.path_changes {
+            let path = tcx.def_path_str(def_id);
+
+            let mut retain = |span: &Span, add: bool| {
+                let id = baseline_id(&path, change.describe(*span, add));
+
+                if ids.contains(id.as_str()) {
+                    found.insert(id);
+                    false
+                } else {
+                    true
+                }
+            };
+
+            let removals = change.removals.iter().copied().filter(|s| retain(s, false));
+            let removals = removals.collect();
+            let additions = change.additions.iter().copied().filter(|s| retain(s, true));
+            let additions = additions.collect();
+
+            change.removals = removals;
+            change.additions = additions;
+        }
+
+        for (&def_id, change) in &mut self.changes {
+            let path = tcx.def_path_str(def_id);
+
+            change.changes.retain(|(type_, _)| {
+                let id = baseline_id(&path, &type_.to_string());
+
+                if ids.contains(id.as_str()) {
+                    found.insert(id);
+                    false
+                } else {
+                    true
+                }
+            });
+
+            change.update_max();
+        }
+
+        self.update_max();
+
+        baseline
+            .iter()
+            .filter(|entry| !found.contains(&entry.id))
+            .cloned()
+            .collect()
+    }
+
+    /// Recompute the most severe change category recorded.
+    fn update_max(&mut self) {
+        let path_max = self
+            .path_changes
+            .values()
+            .map(PathChange::to_category)
+            .max();
+        let change_max = self
+            .changes
+            .values()
+            .filter(|change| change.output)
+            .map(Change::to_category)
+            .max();
+
+        self.max = path_max.max(change_max).unwrap_or_default();
+    }
+
     /// Check whether the changes associated with a `DefId` will be reported.
     pub fn get_output(&self, old: DefId) -> bool {
         self.changes.get(&old).map_or(true, |change| change.output)
@@ -1077,6 +2310,68 @@ impl<'tcx> ChangeSet<'tcx> {
             .map_or(false, |change| change.to_category() == Breaking)
     }
 
+    /// Remove the changes to an item that don't satisfy a predicate, e.g. because they are
+    /// implied by other changes.
+    pub fn retain_changes<F>(&mut self, old: DefId, mut f: F)
+    where
+        F: FnMut(&ChangeType<'tcx>) -> bool,
+    {
+        if let Some(change) = self.changes.get_mut(&old) {
+            change.changes.retain(|(type_, _)| f(type_));
+            change.update_max();
+        }
+
+        self.update_max();
+    }
+
+    /// Remove the type mismatches and bound changes of an item that only stem from a public
+    /// dependency having changed its version, i.e. those between types or traits that have the
+    /// same path in both versions of the dependency.
+    pub fn remove_dependency_mismatches(&mut self, tcx: TyCtxt<'tcx>, old: DefId) {
+        use rustc_middle::ty::print::with_no_trimmed_paths;
+
+        let change = if let Some(change) = self.changes.get_mut(&old) {
+            change
+        } else {
+            return;
+        };
+
+        let same_path = |old: &dyn fmt::Display, new: &dyn fmt::Display| {
+            with_no_trimmed_paths(|| old.to_string() == new.to_string())
+        };
+
+        let (tightened, loosened): (Vec<_>, Vec<_>) = change
+            .changes
+            .iter()
+            .filter_map(|(type_, _)| match type_ {
+                BoundsTightened { pred } => Some((Some(*pred), None)),
+                BoundsLoosened { pred, .. } => Some((None, Some(*pred))),
+                _ => None,
+            })
+            .unzip();
+
+        change.changes.retain(|(type_, _)| match type_ {
+            FnArgTypeChanged { old, new, .. } | FnReturnTypeChanged { old, new } => {
+                !same_path(old, new)
+            }
+            TypeChanged {
+                error: TypeError::Sorts(exp_found),
+            }
+            | TypeChanged {
+                error: TypeError::ArgumentSorts(exp_found, _),
+            } => !same_path(&exp_found.expected, &exp_found.found),
+            TypeChanged {
+                error: TypeError::Traits(exp_found),
+            } => tcx.def_path_str(exp_found.expected) != tcx.def_path_str(exp_found.found),
+            BoundsTightened { pred } => !loosened.iter().flatten().any(|l| same_path(pred, l)),
+            BoundsLoosened { pred, .. } => !tightened.iter().flatten().any(|t| same_path(pred, t)),
+            _ => true,
+        });
+        change.update_max();
+
+        self.update_max();
+    }
+
     /// Check whether a trait item contains breaking changes preventing further analysis of it's
     /// child items.
     pub fn trait_item_breaking(&self, old: DefId) -> bool {
@@ -1085,86 +2380,338 @@ impl<'tcx> ChangeSet<'tcx> {
             .map_or(false, Change::trait_item_breaking)
     }
 
-    fn get_new_version(&self, version: &str) -> Option<String> {
-        if let Ok(mut new_version) = Version::parse(version) {
-            if new_version.major == 0 {
-                new_version.increment_patch();
-            } else {
-                match self.max {
-                    Patch => new_version.increment_patch(),
-                    NonBreaking | TechnicallyBreaking => new_version.increment_minor(),
-                    Breaking => new_version.increment_major(),
-                }
-            }
+    /// Get the most severe change category recorded.
+    pub fn max_category(&self) -> ChangeCategory {
+        self.max
+    }
 
-            Some(format!("{}", new_version))
+    /// Check whether the new crate is a drop-in replacement for the old one, i.e. whether no
+    /// items are missing or incompatible.
+    pub fn is_conforming(&self) -> bool {
+        self.missing().next().is_none() && self.incompatible().next().is_none()
+    }
+
+    /// Compute the new version of the crate, along with notes explaining the decision.
+    pub fn get_new_version(&self, version: &str) -> Option<(String, Vec<String>)> {
+        Version::parse(version)
+            .ok()
+            .map(|version| next_version(version, self.max))
+    }
+
+    /// Check whether the new version of the crate is sufficient, given the changes, i.e. whether
+    /// it is at least the suggested version, and report an error if it isn't.
+    ///
+    /// Returns `None` if either version can't be parsed.
+    pub fn check_version(
+        &self,
+        session: &Session,
+        version: &str,
+        new_version: &str,
+    ) -> Option<bool> {
+        let (required, _) = self.get_new_version(version)?;
+        let required = Version::parse(&required).ok()?;
+        let new_version = Version::parse(new_version).ok()?;
+
+        if new_version >= required {
+            Some(true)
         } else {
-            None
+            session.err(&format!(
+                "insufficient version bump: {} -> ({}) -> {} required, but the current version is {}",
+                version, self.max, required, new_version
+            ));
+
+            Some(false)
         }
     }
 
-    pub fn output_json(&self, session: &Session, version: &str) {
+    pub fn output_json(&self, session: &Session, version: &str, names: Option<(&str, &str)>) {
         #[derive(Serialize)]
         struct Output<'a, 'tcx> {
+            old_name: Option<&'a str>,
+            new_name: Option<&'a str>,
             old_version: String,
             new_version: String,
+            version_notes: Vec<String>,
             changes: RChangeSet<'a, 'tcx>,
         }
 
-        let new_version = self
+        let (new_version, version_notes) = self
             .get_new_version(version)
-            .unwrap_or_else(|| "parse error".to_owned());
+            .unwrap_or_else(|| ("parse error".to_owned(), Vec::new()));
 
         let output = Output {
+            old_name: names.map(|(old, _)| old),
+            new_name: names.map(|(_, new)| new),
             old_version: version.to_owned(),
             new_version,
+            version_notes,
             changes: RChangeSet(session, self),
         };
 
         println!("{}", serde_json::to_string(&output).unwrap());
     }
 
+    /// Format the contents of a change set as a SARIF log.
+    ///
+    /// Every reported change is a result, and every lint reporting changes is a rule, whose
+    /// help text explains the category of the changes.
+    pub fn output_sarif(&self, session: &Session) {
+        let policy = self.lints.policy;
+        let mut rules: Vec<serde_json::Value> = Vec::new();
+        let mut rule_indices = HashMap::new();
+        let mut results = Vec::new();
+        let mut seen = HashSet::new();
+
+        let mut rule_index = |name: &str, help: &str| {
+            *rule_indices
+                .entry(format!("semver::{}", name))
+                .or_insert_with_key(|id| {
+                    rules.push(serde_json::json!({
+                        "id": id,
+                        "name": name,
+                        "help": { "text": help },
+                    }));
+
+                    rules.len() - 1
+                })
+        };
+
+        for &key in self.spans.values() {
+            if !seen.insert(key) {
+                continue;
+            }
+
+            if let Some(change) = self
+                .path_changes
+                .get(&key)
+                .filter(|change| policy.reports(change.to_category()))
+            {
+                for (spans, add) in &[(&change.removals, false), (&change.additions, true)] {
+                    let cat = change.category(*add);
+                    if cat == Patch || spans.is_empty() {
+                        continue;
+                    }
+
+                    let name = if *add { "path_added" } else { "path_removed" };
+                    let index = rule_index(name, PathChange::explanation(*add));
+
+                    for span in spans.iter() {
+                        let related: Vec<_> = Some(change.def_span)
+                            .filter(|def_span| def_span != span)
+                            .map(|def_span| {
+                                sarif_location(session, def_span, Some(1), Some("definition"))
+                            })
+                            .into_iter()
+                            .collect();
+
+                        results.push(serde_json::json!({
+                            "ruleId": format!("semver::{}", name),
+                            "ruleIndex": index,
+                            "level": sarif_level(cat),
+                            "message": {
+                                "text": format!(
+                                    "{} of `{}` ({})",
+                                    change.describe(*span, *add),
+                                    change.name.0,
+                                    cat
+                                ),
+                            },
+                            "locations": [sarif_location(session, *span, None, None)],
+                            "relatedLocations": related,
+                            "properties": { "category": cat },
+                        }));
+                    }
+                }
+            }
+
+            if let Some(change) = self
+                .changes
+                .get(&key)
+                .filter(|change| change.output && policy.reports(change.to_category()))
+            {
+                for (type_, span) in &change.changes {
+                    let cat = change.category(type_);
+                    if cat == Patch || change.is_allowed(cat) {
+                        continue;
+                    }
+
+                    let index = rule_index(type_.lint_name(), type_.explanation());
+                    let mut related = Vec::new();
+
+                    if let Some(old_span) = change.old_span {
+                        related.push(sarif_location(
+                            session,
+                            old_span,
+                            Some(1),
+                            Some("old definition"),
+                        ));
+                    }
+
+                    if span.is_some() {
+                        related.push(sarif_location(
+                            session,
+                            change.new_span,
+                            Some(2),
+                            Some("new definition"),
+                        ));
+                    }
+
+                    results.push(serde_json::json!({
+                        "ruleId": format!("semver::{}", type_.lint_name()),
+                        "ruleIndex": index,
+                        "level": sarif_level(cat),
+                        "message": {
+                            "text": format!("{} in {} ({})", type_, change.name, cat),
+                        },
+                        "locations": [
+                            sarif_location(session, span.unwrap_or(change.new_span), None, None),
+                        ],
+                        "relatedLocations": related,
+                        "properties": { "category": cat },
+                    }));
+                }
+            }
+        }
+
+        let output = serde_json::json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "rust-semverver",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "informationUri": "https://github.com/rust-lang/rust-semverver",
+                        "rules": rules,
+                    },
+                },
+                "results": results,
+            }],
+        });
+
+        println!("{}", serde_json::to_string_pretty(&output).unwrap());
+    }
+
+    /// Format the missing and incompatible items of a replacement crate for user output.
+    ///
+    /// In this mode, the old crate is considered a reference whose API the new crate has to
+    /// provide, so that additions are irrelevant and no version bump is suggested.
+    pub fn output_conformance(&self, session: &Session, verbose: bool) {
+        let missing = self.missing().count();
+        let incompatible = self.incompatible().count();
+
+        if missing == 0 && incompatible == 0 {
+            println!("conformance: replacement provides the reference API");
+        } else {
+            println!(
+                "conformance: {} missing, {} incompatible item(s)",
+                missing, incompatible
+            );
+        }
+
+        for key in self.spans.values() {
+            if let Some(change) = self.path_changes.get(key) {
+                change.report_missing(session);
+            }
+
+            if let Some(change) = self.changes.get(key) {
+                change.report_incompatible(session, verbose);
+            }
+        }
+    }
+
+    /// Format the missing and incompatible items of a replacement crate as JSON.
+    pub fn output_conformance_json(&self, session: &Session, names: Option<(&str, &str)>) {
+        #[derive(Serialize)]
+        struct Output<'a, 'tcx> {
+            reference_name: Option<&'a str>,
+            replacement_name: Option<&'a str>,
+            conforming: bool,
+            missing: RPathChanges<'a>,
+            incompatible: Vec<RChange<'a, 'tcx>>,
+        }
+
+        let mut missing: Vec<_> = self.missing().collect();
+        missing.sort();
+
+        let mut incompatible: Vec<_> = self.incompatible().collect();
+        incompatible.sort();
+        let incompatible: Vec<_> = incompatible
+            .into_iter()
+            .map(|change| RChange(session, change))
+            .collect();
+
+        let output = Output {
+            reference_name: names.map(|(old, _)| old),
+            replacement_name: names.map(|(_, new)| new),
+            conforming: missing.is_empty() && incompatible.is_empty(),
+            missing: RPathChanges(session, missing),
+            incompatible,
+        };
+
+        println!("{}", serde_json::to_string(&output).unwrap());
+    }
+
+    /// Iterate over the path changes of items missing exports in the new crate.
+    fn missing(&self) -> impl Iterator<Item = &PathChange> {
+        self.path_changes
+            .values()
+            .filter(|change| !change.removals.is_empty())
+    }
+
+    /// Iterate over the changes of items that have broken in the new crate.
+    fn incompatible(&self) -> impl Iterator<Item = &Change<'tcx>> {
+        self.changes
+            .values()
+            .filter(|change| change.output && change.max == Breaking)
+    }
+
     /// Format the contents of a change set for user output.
+    ///
+    /// The crate names are only shown if they are known and differ between the two versions.
     pub fn output(
         &self,
         session: &Session,
         version: &str,
+        names: Option<(&str, &str)>,
         verbose: bool,
         compact: bool,
-        api_guidelines: bool,
     ) {
-        if let Some(new_version) = self.get_new_version(version) {
+        if let Some((new_version, notes)) = self.get_new_version(version) {
             if compact {
                 println!("{}", new_version);
             } else {
-                println!(
-                    "version bump: {} -> ({}) -> {}",
-                    version, self.max, new_version
-                );
+                if let Some((old_name, new_name)) = names.filter(|(old, new)| old != new) {
+                    println!(
+                        "version bump ({} -> {}): {} -> ({}) -> {}",
+                        old_name, new_name, version, self.max, new_version
+                    );
+                } else {
+                    println!(
+                        "version bump: {} -> ({}) -> {}",
+                        version, self.max, new_version
+                    );
+                }
+
+                for note in notes {
+                    println!("note: {}", note);
+                }
             }
         } else {
             println!("max change: {}, could not parse {}", self.max, version);
         }
 
+        let policy = self.lints.policy;
+
         for key in self.spans.values() {
             if let Some(change) = self.path_changes.get(key) {
-                if api_guidelines {
-                    match change.to_category() {
-                        Patch | Breaking => change.report(session),
-                        _ => (),
-                    }
-                } else {
+                if policy.reports(change.to_category()) {
                     change.report(session);
                 }
             }
 
             if let Some(change) = self.changes.get(key) {
-                if api_guidelines {
-                    match change.to_category() {
-                        Patch | Breaking => change.report(session, verbose),
-                        _ => (),
-                    }
-                } else {
+                if policy.reports(change.to_category()) {
                     change.report(session, verbose);
                 }
             }
@@ -1198,7 +2745,14 @@ impl<'a, 'tcx> Serialize for RChangeSet<'a, 'tcx> {
     {
         let mut state = serializer.serialize_struct("ChangeSet", 3)?;
 
-        let path_changes: Vec<_> = self.1.path_changes.values().collect();
+        let policy = self.1.lints.policy;
+
+        let path_changes: Vec<_> = self
+            .1
+            .path_changes
+            .values()
+            .filter(|c| policy.reports(c.to_category()))
+            .collect();
         state.serialize_field("path_changes", &RPathChanges(self.0, path_changes))?;
 
         let changes: Vec<_> = self
@@ -1206,7 +2760,10 @@ impl<'a, 'tcx> Serialize for RChangeSet<'a, 'tcx> {
             .changes
             .values()
             .filter_map(|c| {
-                if c.output && !c.changes.is_empty() {
+                if c.output
+                    && c.changes.iter().any(|(t, _)| c.category(t) > Patch)
+                    && policy.reports(c.to_category())
+                {
                     Some(RChange(self.0, c))
                 } else {
                     None
@@ -1263,7 +2820,7 @@ pub mod tests {
 
     impl Arbitrary for DefId_ {
         fn arbitrary(g: &mut Gen) -> DefId_ {
-            use rustc_hir::def_id::{CrateNum, DefIndex};
+            use rustc_hir::def_id::DefIndex;
 
             // NOTE: These inner types are defined via `rustc_index::newtype_index`
             // macro which leaves trailing 256 values for possible enum-packing
@@ -1461,7 +3018,12 @@ pub mod tests {
         changes: Vec<(ChangeType_, Option<Span_>)>,
     ) -> Change<'a> {
         let mut interner = Interner::default();
-        let mut change = Change::new(Name::Symbol(RSymbol(interner.intern("test"))), s1, output);
+        let mut change = Change::new(
+            Name::Symbol(RSymbol(interner.intern("test"))),
+            s1,
+            output,
+            Rc::default(),
+        );
 
         for (type_, span) in changes {
             change.insert(type_.inner(), span.map(|s| s.inner()));
@@ -1476,7 +3038,7 @@ pub mod tests {
     /// Construct `PathChange`s from things that can be generated.
     fn build_path_change(s1: Span, spans: Vec<(bool, Span)>) -> PathChange {
         let mut interner = Interner::default();
-        let mut change = PathChange::new(interner.intern("test"), s1);
+        let mut change = PathChange::new(interner.intern("test"), s1, Rc::default());
 
         for (add, span) in spans {
             change.insert(span, add);
@@ -1699,4 +3261,68 @@ pub mod tests {
         })
         }
     }
+
+    /// Compute the version following the given one, as a string.
+    fn next(version: &str, max: ChangeCategory) -> String {
+        next_version(Version::parse(version).unwrap(), max).0
+    }
+
+    #[test]
+    fn next_version_stable() {
+        assert_eq!(next("1.2.3", Patch), "1.2.4");
+        assert_eq!(next("1.2.3", NonBreaking), "1.3.0");
+        assert_eq!(next("1.2.3", TechnicallyBreaking), "1.3.0");
+        assert_eq!(next("1.2.3", Breaking), "2.0.0");
+    }
+
+    #[test]
+    fn next_version_unstable() {
+        assert_eq!(next("0.3.4", Patch), "0.3.5");
+        assert_eq!(next("0.3.4", TechnicallyBreaking), "0.3.5");
+        assert_eq!(next("0.3.4", Breaking), "0.4.0");
+        assert_eq!(next("0.0.3", Patch), "0.0.4");
+        assert_eq!(next("0.0.3", Breaking), "0.0.4");
+    }
+
+    #[test]
+    fn next_version_prerelease() {
+        assert_eq!(next("1.0.0-alpha.3", Breaking), "1.0.0-alpha.4");
+        assert_eq!(next("1.0.0-alpha", Patch), "1.0.0-alpha.1");
+        assert_eq!(next("1.2.0-rc.1", NonBreaking), "1.2.0-rc.2");
+        assert_eq!(next("1.2.0-rc.1", Breaking), "2.0.0");
+        assert_eq!(next("1.2.3-beta.2", NonBreaking), "1.3.0");
+        assert_eq!(next("0.4.0-alpha.1", Breaking), "0.4.0-alpha.2");
+    }
+
+    /// Every lint name a change type can be reported by is listed in `LINTS`, and vice-versa.
+    #[test]
+    fn lints_listed() {
+        let source = include_str!("changes.rs");
+        let start = source.find("pub fn lint_name(").unwrap();
+        let end = start + source[start..].find("\n    }\n").unwrap();
+
+        let names: BTreeSet<_> = source[start..end]
+            .split("=> \"")
+            .skip(1)
+            .map(|rest| &rest[..rest.find('"').unwrap()])
+            .collect();
+
+        for name in &names {
+            assert!(LINTS.contains(name), "lint `{}` missing from `LINTS`", name);
+        }
+
+        for name in LINTS {
+            assert!(
+                names.contains(name) || name.starts_with("path_"),
+                "`LINTS` entry `{}` isn't a lint name",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn next_version_build_metadata() {
+        assert_eq!(next("1.2.3+build.5", Patch), "1.2.4");
+        assert_eq!(next("1.0.0-rc.1+build.5", Patch), "1.0.0-rc.2");
+    }
 }
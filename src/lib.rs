@@ -22,4 +22,6 @@ mod translate;
 mod traverse;
 mod typeck;
 
-pub use self::traverse::{run_analysis, run_traversal};
+pub use self::changes::{BaselineEntry, ChangeCategory, LintLevels, Policy};
+pub use self::mapping::DependencyVersions;
+pub use self::traverse::{run_analysis, run_traversal, AnalysisOptions};
@@ -10,10 +10,10 @@ use rustc_hir::{
 };
 use rustc_middle::{
     hir::exports::Export,
-    ty::{AssocKind, GenericParamDef, GenericParamDefKind},
+    ty::{AssocKind, DefIdTree, GenericParamDef, GenericParamDefKind, TyCtxt},
 };
 use rustc_span::symbol::Symbol;
-use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::hash::{Hash, Hasher};
 
 /// A description of an item found in an inherent impl.
@@ -71,6 +71,11 @@ pub struct IdMapping {
     old_crate: CrateNum,
     /// The new crate.
     new_crate: CrateNum,
+    /// Dependency crates of the old crate mapped to their counterparts used by the new crate.
+    ///
+    /// Items re-exported from these are analyzed as if they were part of the crates
+    /// themselves.
+    dependency_crates: BTreeMap<CrateNum, CrateNum>,
     /// Toplevel items' old `DefId` mapped to old and new `Res`.
     toplevel_mapping: HashMap<DefId, (Res, Res)>,
     /// The set of items that have been removed or added and thus have no corresponding item in
@@ -78,8 +83,13 @@ pub struct IdMapping {
     non_mapped_items: HashSet<DefId>,
     /// Trait items' old `DefId` mapped to old and new `Res`, and the enclosing trait's `DefId`.
     trait_item_mapping: HashMap<DefId, (Res, Res, DefId)>,
+    /// Trait items moved to a supertrait, by old `DefId`, and whether their old trait is
+    /// sealed.
+    moved_trait_items: BTreeMap<DefId, bool>,
     /// The set of private traits in both crates.
     private_traits: HashSet<DefId>,
+    /// The set of items in both crates that have been excluded from the analysis.
+    excluded_items: HashSet<DefId>,
     /// Other items' old `DefId` mapped to new `DefId`.
     internal_mapping: HashMap<DefId, DefId>,
     /// Children mapping, allowing us to enumerate descendants in `AdtDef`s.
@@ -98,10 +108,13 @@ impl IdMapping {
         Self {
             old_crate,
             new_crate,
+            dependency_crates: BTreeMap::new(),
             toplevel_mapping: HashMap::new(),
             non_mapped_items: HashSet::new(),
             trait_item_mapping: HashMap::new(),
+            moved_trait_items: BTreeMap::new(),
             private_traits: HashSet::new(),
+            excluded_items: HashSet::new(),
             internal_mapping: HashMap::new(),
             child_mapping: HashMap::new(),
             reverse_mapping: HashMap::new(),
@@ -110,6 +123,36 @@ impl IdMapping {
         }
     }
 
+    /// Register two dependency crates representing the same upstream crate across versions.
+    ///
+    /// This is only done if the two versions of the re-exporting crate resolve to different
+    /// upstream crates, and the pairing is consistent with the ones registered so far.
+    pub fn add_dependency_crates(&mut self, old: CrateNum, new: CrateNum) -> bool {
+        let local_crates = [self.old_crate, self.new_crate];
+
+        if old == new || local_crates.contains(&old) || local_crates.contains(&new) {
+            return false;
+        }
+
+        if let Some(&mapped) = self.dependency_crates.get(&old) {
+            return mapped == new;
+        }
+
+        // a crate can only have a single counterpart, and can't be on both sides
+        let known_new = |krate| self.dependency_crates.values().any(|&c| c == krate);
+        if known_new(old) || known_new(new) || self.dependency_crates.contains_key(&new) {
+            return false;
+        }
+
+        self.dependency_crates.insert(old, new);
+        true
+    }
+
+    /// Iterate over the pairs of dependency crates registered.
+    pub fn dependency_crates(&self) -> impl Iterator<Item = (CrateNum, CrateNum)> + '_ {
+        self.dependency_crates.iter().map(|(&old, &new)| (old, new))
+    }
+
     /// Register two exports representing the same item across versions.
     pub fn add_export(&mut self, old: Res, new: Res) -> bool {
         let (old_def_id, new_def_id) =
@@ -145,6 +188,21 @@ impl IdMapping {
         self.reverse_mapping.insert(new.def_id(), old_def_id);
     }
 
+    /// Register a trait item that has been moved to a supertrait of its trait.
+    ///
+    /// The item itself needs to be registered using `add_trait_item` as well.
+    pub fn add_moved_trait_item(&mut self, old: DefId, sealed_trait: bool) {
+        self.moved_trait_items.insert(old, sealed_trait);
+    }
+
+    /// Iterate over the old `DefId`s of trait items moved to a supertrait, and whether their
+    /// old trait is sealed.
+    pub fn moved_trait_items(&self) -> impl Iterator<Item = (DefId, bool)> + '_ {
+        self.moved_trait_items
+            .iter()
+            .map(|(&old, &sealed)| (old, sealed))
+    }
+
     /// Add a private trait's `DefId`.
     pub fn add_private_trait(&mut self, trait_def_id: DefId) {
         self.private_traits.insert(trait_def_id);
@@ -245,6 +303,25 @@ impl IdMapping {
         }
     }
 
+    /// Get the new `DefId` of an item in an old inherent impl, by its name and kind.
+    pub fn get_new_inherent_item(&self, old: DefId) -> Option<DefId> {
+        let (entry, _) = self
+            .inherent_items
+            .iter()
+            .find(|(_, items)| items.iter().any(|&(_, item)| item == old))?;
+
+        let new_entry = InherentEntry {
+            parent_def_id: self.get_new_id(entry.parent_def_id)?,
+            kind: entry.kind,
+            name: entry.name,
+        };
+
+        self.inherent_items
+            .get(&new_entry)
+            .and_then(|items| items.iter().next())
+            .map(|&(_, item)| item)
+    }
+
     /// Get the old `DefId` associated with the given new one.
     pub fn get_old_id(&self, new: DefId) -> Option<DefId> {
         assert!(!self.in_old_crate(new));
@@ -266,6 +343,18 @@ impl IdMapping {
         self.private_traits.contains(&trait_def_id)
     }
 
+    /// Register an item excluded from the analysis.
+    pub fn add_excluded(&mut self, def_id: DefId) {
+        self.excluded_items.insert(def_id);
+    }
+
+    /// Check whether the given `DefId` has been excluded from the analysis, either by itself or
+    /// by being defined in an excluded module.
+    pub fn is_excluded(&self, tcx: TyCtxt, def_id: DefId) -> bool {
+        std::iter::successors(Some(def_id), |&did| tcx.parent(did))
+            .any(|did| self.excluded_items.contains(&did))
+    }
+
     /// Check whether an old `DefId` is present in the mappings.
     pub fn contains_old_id(&self, old: DefId) -> bool {
         self.toplevel_mapping.contains_key(&old)
@@ -303,9 +392,10 @@ impl IdMapping {
         self.inherent_items.iter()
     }
 
-    /// Check whether a `DefId` belongs to an item in the old crate.
+    /// Check whether a `DefId` belongs to an item in the old crate, or one of its dependencies
+    /// that is mapped to a different crate in the new version.
     pub fn in_old_crate(&self, did: DefId) -> bool {
-        self.old_crate == did.krate
+        self.old_crate == did.krate || self.dependency_crates.contains_key(&did.krate)
     }
 
     /// Get the old crate's `CrateNum`.
@@ -313,9 +403,10 @@ impl IdMapping {
         self.old_crate
     }
 
-    /// Check whether a `DefId` belongs to an item in the new crate.
+    /// Check whether a `DefId` belongs to an item in the new crate, or one of its dependencies
+    /// that is mapped to a different crate in the old version.
     pub fn in_new_crate(&self, did: DefId) -> bool {
-        self.new_crate == did.krate
+        self.new_crate == did.krate || self.dependency_crates.values().any(|&c| c == did.krate)
     }
 
     /// Get the new crate's `CrateNum`.
@@ -324,6 +415,51 @@ impl IdMapping {
     }
 }
 
+/// The versions of the dependencies used to build the two crate versions.
+///
+/// This information is not available to the compiler, so it has to be passed in by the
+/// caller, e.g. `cargo semver`, as lists of `name=version` pairs.
+#[derive(Debug, Default)]
+pub struct DependencyVersions {
+    /// The old crate's dependencies' versions, by crate name.
+    old: HashMap<String, String>,
+    /// The new crate's dependencies' versions, by crate name.
+    new: HashMap<String, String>,
+}
+
+impl DependencyVersions {
+    /// Construct the dependency version info from two comma-separated lists of `name=version`
+    /// pairs.
+    pub fn parse(old: &str, new: &str) -> Self {
+        fn parse_list(list: &str) -> HashMap<String, String> {
+            list.split(',')
+                .filter_map(|entry| {
+                    let mut split = entry.splitn(2, '=');
+                    let name = split.next()?.trim();
+                    let version = split.next()?.trim();
+
+                    Some((name.replace('-', "_"), version.to_owned()))
+                })
+                .collect()
+        }
+
+        Self {
+            old: parse_list(old),
+            new: parse_list(new),
+        }
+    }
+
+    /// Get the version of a dependency of the old crate.
+    pub fn get_old(&self, name: &str) -> Option<&String> {
+        self.old.get(name)
+    }
+
+    /// Get the version of a dependency of the new crate.
+    pub fn get_new(&self, name: &str) -> Option<&String> {
+        self.new.get(name)
+    }
+}
+
 /// An export that could be missing from one of the crate versions.
 type OptionalExport = Option<Export<HirId>>;
 
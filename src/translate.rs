@@ -3,11 +3,12 @@
 
 use crate::mapping::{IdMapping, InherentEntry};
 use log::{debug, info};
+use rustc_hir::def::DefKind;
 use rustc_hir::def_id::DefId;
 use rustc_infer::infer::InferCtxt;
 use rustc_middle::ty::{
     fold::{BottomUpFolder, TypeFoldable, TypeFolder},
-    subst::{GenericArg, InternalSubsts, SubstsRef},
+    subst::{GenericArg, InternalSubsts, Subst, SubstsRef},
     GenericParamDefKind, ParamEnv, Predicate, Region, TraitRef, Ty, TyCtxt,
 };
 use std::collections::HashMap;
@@ -176,8 +177,17 @@ impl<'a, 'tcx> TranslationContext<'a, 'tcx> {
                         // we fold bottom-up, so the code above is invalid, as it assumes the
                         // substs (that have been folded already) are yet untranslated
                         if let Some(target_def_id) = (self.translate_orig)(self.id_mapping, *did) {
-                            let target_adt = self.tcx.adt_def(target_def_id);
-                            self.tcx.mk_adt(target_adt, substs)
+                            if self.tcx.def_kind(target_def_id) == DefKind::TyAlias {
+                                // the ADT has been replaced by a type alias, use its expansion
+                                if self.tcx.generics_of(target_def_id).count() == substs.len() {
+                                    self.tcx.type_of(target_def_id).subst(self.tcx, substs)
+                                } else {
+                                    ty
+                                }
+                            } else {
+                                let target_adt = self.tcx.adt_def(target_def_id);
+                                self.tcx.mk_adt(target_adt, substs)
+                            }
                         } else {
                             ty
                         }
@@ -519,7 +529,19 @@ impl<'a, 'tcx> TranslationContext<'a, 'tcx> {
 
     /// Translate an `InherentEntry`.
     pub fn translate_inherent_entry(&self, orig_entry: &InherentEntry) -> Option<InherentEntry> {
+        use rustc_middle::ty::TyKind;
+
         (self.translate_orig)(self.id_mapping, orig_entry.parent_def_id).map(|parent_def_id| {
+            // inherent items of an ADT replaced by a type alias are found on the alias' expansion
+            let parent_def_id = if self.tcx.def_kind(parent_def_id) == DefKind::TyAlias {
+                match *self.tcx.type_of(parent_def_id).kind() {
+                    TyKind::Adt(def, _) => def.did,
+                    _ => parent_def_id,
+                }
+            } else {
+                parent_def_id
+            };
+
             InherentEntry {
                 parent_def_id,
                 kind: orig_entry.kind,
@@ -10,15 +10,18 @@
 //! in a fourth pass that uses trait bounds to find matching impls.
 
 use crate::{
-    changes::{ChangeSet, ChangeType},
-    mapping::{IdMapping, NameMapping},
+    changes::{
+        Allowance, Breaking, ChangeSet, ChangeType, CtorChange, LintLevels, NonBreaking,
+        TechnicallyBreaking, TurbofishChange,
+    },
+    mapping::{DependencyVersions, IdMapping, InherentEntry, NameMapping},
     mismatch::MismatchRelation,
     translate::TranslationContext,
     typeck::{BoundContext, TypeComparisonContext},
 };
 use log::{debug, info};
 use rustc_hir::def::{CtorKind, CtorOf, DefKind, Res, Res::Def};
-use rustc_hir::def_id::DefId;
+use rustc_hir::def_id::{CrateNum, DefId};
 use rustc_hir::hir_id::HirId;
 use rustc_hir::lang_items::LangItem;
 use rustc_infer::infer::TyCtxtInferExt;
@@ -26,24 +29,48 @@ use rustc_middle::{
     hir::exports::Export,
     ty::{
         subst::{InternalSubsts, Subst},
-        AssocItem, GenericParamDef, GenericParamDefKind, Generics, TraitRef, Ty, TyCtxt, TyKind,
-        TypeAndMut, Visibility,
+        AssocItem, Binder, DefIdTree, FieldDef, FnSig, GenericParamDef, GenericParamDefKind,
+        Generics, PolyFnSig, TraitRef, Ty, TyCtxt, TyKind, TypeAndMut, VariantDef, Visibility,
         Visibility::Public,
     },
 };
 use rustc_mir::const_eval::is_const_fn;
-use std::collections::{BTreeMap, HashSet, VecDeque};
+use rustc_span::symbol::{kw, sym, Symbol};
+use rustc_trait_selection::traits::supertrait_def_ids;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+
+/// Options controlling which items are analyzed and reported.
+#[derive(Debug, Default)]
+pub struct AnalysisOptions {
+    /// The paths of the modules and items to restrict the analysis to, if any.
+    ///
+    /// Everything reachable from these is analyzed as well.
+    pub paths: Vec<String>,
+    /// Patterns matching the paths of items to be skipped.
+    pub excludes: Vec<String>,
+    /// Whether to treat `#[doc(hidden)]` items, and everything beneath them, as private.
+    pub doc_hidden_private: bool,
+    /// The lint levels overriding the default categories of changes.
+    pub lints: LintLevels,
+}
 
 /// The main entry point to our analysis passes.
 ///
 /// Set up the necessary data structures and run the analysis passes and call the actual passes.
-pub fn run_analysis(tcx: TyCtxt, old: DefId, new: DefId) -> ChangeSet {
+pub fn run_analysis<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    old: DefId,
+    new: DefId,
+    dependency_versions: &DependencyVersions,
+    options: &AnalysisOptions,
+) -> ChangeSet<'tcx> {
     let mut changes = ChangeSet::default();
+    changes.set_lint_levels(options.lints.clone());
     let mut id_mapping = IdMapping::new(old.krate, new.krate);
 
     // first pass
     debug!("first pass started");
-    diff_structure(&mut changes, &mut id_mapping, tcx, old, new);
+    diff_structure(&mut changes, &mut id_mapping, tcx, old, new, options);
 
     // second pass
     debug!("second pass started");
@@ -59,14 +86,154 @@ pub fn run_analysis(tcx: TyCtxt, old: DefId, new: DefId) -> ChangeSet {
         diff_types(&mut changes, &id_mapping, tcx, old, new);
     }
 
+    diff_moved_trait_items(&mut changes, &id_mapping, tcx);
+
     // fourth pass on impls
     debug!("fourth pass started");
-    diff_inherent_impls(&mut changes, &id_mapping, tcx);
-    diff_trait_impls(&mut changes, &id_mapping, tcx);
+    diff_inherent_impls(&mut changes, &id_mapping, tcx, options.doc_hidden_private);
+    diff_trait_impls(&mut changes, &id_mapping, tcx, !options.paths.is_empty());
+
+    // check public signatures for dependencies that changed their version
+    for (old, new) in id_mapping.items() {
+        diff_dependencies(
+            &mut changes,
+            &id_mapping,
+            tcx,
+            dependency_versions,
+            old,
+            new,
+        );
+    }
+
+    // acknowledge the changes allowed by attributes on the items of the new crate
+    let allowances: Vec<_> = changes
+        .change_ids()
+        .filter_map(|def_id| {
+            let new_def_id = if id_mapping.in_new_crate(def_id) {
+                Some(def_id)
+            } else {
+                id_mapping
+                    .get_new_id(def_id)
+                    .or_else(|| id_mapping.get_new_inherent_item(def_id))
+            };
+
+            new_def_id
+                .and_then(|did| get_allowance(tcx, did))
+                .map(|allowance| (def_id, allowance))
+        })
+        .collect();
+    changes.allow_changes(allowances);
+
+    // attribute changes to items re-exported from dependencies to their upstream crates
+    for (old_crate, new_crate) in id_mapping.dependency_crates() {
+        changes.set_upstream_crate(old_crate, tcx.crate_name(old_crate));
+        changes.set_upstream_crate(new_crate, tcx.crate_name(new_crate));
+    }
 
     changes
 }
 
+/// Find the module with the given path, relative to a crate's root module, along with its
+/// global visibility, which is only public if all modules on the path are.
+fn resolve_module(tcx: TyCtxt, root: DefId, segments: &[Symbol]) -> Option<(DefId, Visibility)> {
+    use rustc_hir::def::DefKind::Mod;
+
+    segments
+        .iter()
+        .try_fold((root, Public), |(module, vis), &segment| {
+            tcx.item_children(module)
+                .iter()
+                .find_map(|child| match child.res {
+                    Res::Def(Mod, def_id) if child.ident.name == segment => {
+                        Some((def_id, get_vis(vis, *child)))
+                    }
+                    _ => None,
+                })
+        })
+}
+
+/// Check whether an item path matches a pattern, in which `*` matches any sequence of
+/// characters, including path separators.
+fn glob_matches(pattern: &str, path: &str) -> bool {
+    let pattern = pattern.trim_start_matches("crate::").as_bytes();
+    let path = path.as_bytes();
+
+    let (mut p, mut s) = (0, 0);
+    // the position of the last `*` in the pattern and of the path byte it currently matches up to
+    let mut backtrack = None;
+
+    while s < path.len() {
+        if p < pattern.len() && pattern[p] == b'*' {
+            backtrack = Some((p, s));
+            p += 1;
+        } else if p < pattern.len() && pattern[p] == path[s] {
+            p += 1;
+            s += 1;
+        } else if let Some((star_p, star_s)) = backtrack {
+            backtrack = Some((star_p, star_s + 1));
+            p = star_p + 1;
+            s = star_s + 1;
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == b'*')
+}
+
+/// Get the changes acknowledged by a `#[semverver::allow(..)]` attribute on an item.
+///
+/// The attribute lists the most severe category of changes allowed, i.e. one of `breaking`,
+/// `technically_breaking` and `non_breaking`, defaulting to `breaking`, and optionally a
+/// `reason = "..."`.
+fn get_allowance(tcx: TyCtxt, def_id: DefId) -> Option<Allowance> {
+    let attr = tcx.get_attrs(def_id).iter().find(|attr| {
+        !attr.is_doc_comment() && {
+            let segments: Vec<_> = attr
+                .get_normal_item()
+                .path
+                .segments
+                .iter()
+                .map(|segment| segment.ident.name.as_str())
+                .collect();
+
+            segments.len() == 2 && segments[0] == "semverver" && segments[1] == "allow"
+        }
+    })?;
+
+    let mut allowance = Allowance {
+        category: Breaking,
+        reason: None,
+    };
+    let mut categories = Vec::new();
+
+    for item in attr.meta_item_list().unwrap_or_default() {
+        match &*item.name_or_empty().as_str() {
+            "breaking" => categories.push(Breaking),
+            "technically_breaking" => categories.push(TechnicallyBreaking),
+            "non_breaking" => categories.push(NonBreaking),
+            "reason" => allowance.reason = item.value_str().map(|r| r.to_string()),
+            _ => (),
+        }
+    }
+
+    if let Some(category) = categories.into_iter().max() {
+        allowance.category = category;
+    }
+
+    Some(allowance)
+}
+
+/// Check whether an item is marked `#[doc(hidden)]`.
+fn is_doc_hidden(tcx: TyCtxt, def_id: DefId) -> bool {
+    tcx.get_attrs(def_id).iter().any(|attr| {
+        attr.has_name(sym::doc)
+            && attr.meta_item_list().map_or(false, |items| {
+                items.iter().any(|item| item.has_name(sym::hidden))
+            })
+    })
+}
+
 // Get the visibility of the inner item, given the outer item's visibility.
 fn get_vis(outer_vis: Visibility, def: Export<HirId>) -> Visibility {
     if outer_vis == Public {
@@ -121,7 +288,8 @@ pub fn run_traversal(tcx: TyCtxt, new: DefId) {
 ///
 /// Traverse the two root modules in an interleaved manner, matching up pairs of modules
 /// from the two crate versions and compare for changes. Matching children get processed
-/// in the same fashion.
+/// in the same fashion. If any paths are given in the `options`, the traversal starts from
+/// the items they point to instead, and excluded items are skipped.
 #[allow(clippy::cognitive_complexity)]
 fn diff_structure<'tcx>(
     changes: &mut ChangeSet,
@@ -129,37 +297,125 @@ fn diff_structure<'tcx>(
     tcx: TyCtxt<'tcx>,
     old: DefId,
     new: DefId,
+    options: &AnalysisOptions,
 ) {
     use rustc_hir::def::DefKind::*;
 
+    // Get the visibility of an item, treating `#[doc(hidden)]` items as private if requested.
+    let export_vis = |outer_vis, def: Export<HirId>| {
+        let hidden = options.doc_hidden_private
+            && def
+                .res
+                .opt_def_id()
+                .map_or(false, |did| is_doc_hidden(tcx, did));
+
+        if hidden {
+            Visibility::Invisible
+        } else {
+            get_vis(outer_vis, def)
+        }
+    };
+
     let mut visited = HashSet::new();
     let mut children = NameMapping::default();
     let mut mod_queue = VecDeque::new();
     let mut traits = Vec::new();
+    // Struct constructors are compared once the structs they belong to have been matched up.
+    let mut ctors = Vec::new();
     // Additions and removals are processed with a delay to avoid creating multiple path change
     // entries. This is necessary, since the order in which added or removed paths are found wrt
     // each other and their item's definition can't be relied upon.
     let mut removals = Vec::new();
     let mut additions = Vec::new();
 
-    // Start off with the root module pair.
-    mod_queue.push_back((old, new, Public, Public));
+    // Start off with the root module pair, or the parent modules of the requested items, in
+    // which case only the children with the requested name are considered.
+    if options.paths.is_empty() {
+        mod_queue.push_back((old, new, Public, Public, Vec::new(), None));
+    }
+
+    for path in &options.paths {
+        let mut segments: Vec<_> = path
+            .trim_start_matches("crate::")
+            .split("::")
+            .map(Symbol::intern)
+            .collect();
+        let name = segments.pop();
+
+        match (
+            resolve_module(tcx, old, &segments),
+            resolve_module(tcx, new, &segments),
+        ) {
+            (Some((o_def_id, o_vis)), Some((n_def_id, n_vis))) => {
+                mod_queue.push_back((o_def_id, n_def_id, o_vis, n_vis, segments, name));
+            }
+            _ => {
+                tcx.sess.err(&format!(
+                    "could not find path `{}` in both crate versions",
+                    path
+                ));
+            }
+        }
+    }
+
+    // Pull a matched module pair from the queue, with the modules' global visibility and path.
+    while let Some((old_def_id, new_def_id, old_vis, new_vis, mod_path, name)) =
+        mod_queue.pop_front()
+    {
+        let select = |children: &[Export<HirId>]| -> Vec<_> {
+            children
+                .iter()
+                .filter(|child| name.map_or(true, |name| child.ident.name == name))
+                .copied()
+                .collect()
+        };
 
-    // Pull a matched module pair from the queue, with the modules' global visibility.
-    while let Some((old_def_id, new_def_id, old_vis, new_vis)) = mod_queue.pop_front() {
         children.add(
-            tcx.item_children(old_def_id).to_vec(), // TODO: clean up
-            tcx.item_children(new_def_id).to_vec(),
+            select(tcx.item_children(old_def_id)),
+            select(tcx.item_children(new_def_id)),
         );
 
         for items in children.drain() {
+            if let Some(item) = items.0.or(items.1) {
+                let path = mod_path
+                    .iter()
+                    .chain(Some(&item.ident.name))
+                    .map(|segment| segment.as_str().to_string())
+                    .collect::<Vec<_>>()
+                    .join("::");
+
+                if options
+                    .excludes
+                    .iter()
+                    .any(|pattern| glob_matches(pattern, &path))
+                {
+                    for export in items.0.iter().chain(items.1.iter()) {
+                        if let Some(def_id) = export.res.opt_def_id() {
+                            id_mapping.add_excluded(def_id);
+                        }
+                    }
+
+                    continue;
+                }
+            }
+
             match items {
                 // an item pair is found
                 (Some(o), Some(n)) => {
+                    // items re-exported from dependencies are analyzed as well, provided the
+                    // two versions resolve to different upstream crates
+                    if let (Some(o_def_id), Some(n_def_id)) =
+                        (o.res.opt_def_id(), n.res.opt_def_id())
+                    {
+                        if export_vis(old_vis, o) == Public || export_vis(new_vis, n) == Public {
+                            id_mapping.add_dependency_crates(o_def_id.krate, n_def_id.krate);
+                        }
+                    }
+
                     if let (Def(Mod, o_def_id), Def(Mod, n_def_id)) = (o.res, n.res) {
                         if visited.insert((o_def_id, n_def_id)) {
-                            let o_vis = get_vis(old_vis, o);
-                            let n_vis = get_vis(new_vis, n);
+                            let o_vis = export_vis(old_vis, o);
+                            let n_vis = export_vis(new_vis, n);
 
                             if o_vis != n_vis {
                                 changes.new_change(
@@ -180,20 +436,27 @@ fn diff_structure<'tcx>(
                                 }
                             }
 
-                            mod_queue.push_back((o_def_id, n_def_id, o_vis, n_vis));
+                            let mut o_path = mod_path.clone();
+                            o_path.push(o.ident.name);
+
+                            mod_queue.push_back((o_def_id, n_def_id, o_vis, n_vis, o_path, None));
                         }
                     } else if id_mapping.add_export(o.res, n.res) {
-                        // struct constructors are weird/hard - let's go shopping!
+                        // struct constructors get their own treatment, see `diff_ctors`
                         if let (Def(Ctor(CtorOf::Struct, _), _), Def(Ctor(CtorOf::Struct, _), _)) =
                             (o.res, n.res)
                         {
+                            ctors.push((
+                                Some((o.res.def_id(), export_vis(old_vis, o))),
+                                Some((n.res.def_id(), export_vis(new_vis, n))),
+                            ));
                             continue;
                         }
 
                         let o_def_id = o.res.def_id();
                         let n_def_id = n.res.def_id();
-                        let o_vis = get_vis(old_vis, o);
-                        let n_vis = get_vis(new_vis, n);
+                        let o_vis = export_vis(old_vis, o);
+                        let n_vis = export_vis(new_vis, n);
 
                         let output = o_vis == Public || n_vis == Public;
                         changes.new_change(
@@ -268,7 +531,14 @@ fn diff_structure<'tcx>(
                             // fields
                             (Struct, Struct) | (Union, Union) | (Enum, Enum) => {
                                 diff_generics(changes, id_mapping, tcx, false, o_def_id, n_def_id);
-                                diff_adts(changes, id_mapping, tcx, o.res, n.res);
+                                diff_adts(
+                                    changes,
+                                    id_mapping,
+                                    tcx,
+                                    options.doc_hidden_private,
+                                    o.res,
+                                    n.res,
+                                );
                             }
                             // trait definitions can declare generics and require us to check
                             // for trait item addition and removal, as well as changes to their
@@ -287,6 +557,34 @@ fn diff_structure<'tcx>(
                                 diff_generics(changes, id_mapping, tcx, false, o_def_id, n_def_id);
                                 traits.push((o_def_id, n_def_id, output));
                             }
+                            // an ADT replaced by a type alias is compared structurally against
+                            // the ADT the alias expands to
+                            (Struct, TyAlias) | (Union, TyAlias) | (Enum, TyAlias) => {
+                                if let Some(target) = expand_alias(tcx, o_kind, n_def_id) {
+                                    let c = ChangeType::AliasKindChanged { now_alias: true };
+                                    changes.add_change(c, o_def_id, None);
+                                    diff_generics(
+                                        changes, id_mapping, tcx, false, o_def_id, n_def_id,
+                                    );
+                                    diff_adts(
+                                        changes,
+                                        id_mapping,
+                                        tcx,
+                                        options.doc_hidden_private,
+                                        o.res,
+                                        target,
+                                    );
+                                } else {
+                                    changes.add_change(ChangeType::KindDifference, o_def_id, None);
+                                }
+                            }
+                            // a type alias replaced by an ADT only needs its expansion to be
+                            // compared with the new type, which happens in the third pass
+                            (TyAlias, Struct) | (TyAlias, Union) | (TyAlias, Enum) => {
+                                let c = ChangeType::AliasKindChanged { now_alias: false };
+                                changes.add_change(c, o_def_id, None);
+                                diff_generics(changes, id_mapping, tcx, false, o_def_id, n_def_id);
+                            }
                             // a non-matching item pair - register the change and abort further
                             // analysis of it
                             _ => {
@@ -297,24 +595,24 @@ fn diff_structure<'tcx>(
                 }
                 // only an old item is found
                 (Some(o), None) => {
-                    // struct constructors are weird/hard - let's go shopping!
                     if let Def(Ctor(CtorOf::Struct, _), _) = o.res {
+                        ctors.push((Some((o.res.def_id(), export_vis(old_vis, o))), None));
                         continue;
                     }
 
-                    if get_vis(old_vis, o) == Public {
+                    if export_vis(old_vis, o) == Public {
                         // delay the handling of removals until the id mapping is complete
                         removals.push(o);
                     }
                 }
                 // only a new item is found
                 (None, Some(n)) => {
-                    // struct constructors are weird/hard - let's go shopping!
                     if let Def(Ctor(CtorOf::Struct, _), _) = n.res {
+                        ctors.push((None, Some((n.res.def_id(), export_vis(new_vis, n)))));
                         continue;
                     }
 
-                    if get_vis(new_vis, n) == Public {
+                    if export_vis(new_vis, n) == Public {
                         debug!("addition: {:?} ({:?})", new_vis, n);
                         // delay the handling of additions until the id mapping is complete
                         additions.push(n);
@@ -352,11 +650,127 @@ fn diff_structure<'tcx>(
         }
     }
 
+    // a constructor is exported along every path of its struct, so dedup the changes found
+    let ctor_changes: BTreeSet<_> = ctors
+        .into_iter()
+        .filter_map(|(old_ctor, new_ctor)| diff_ctors(id_mapping, tcx, old_ctor, new_ctor))
+        .collect();
+
+    for (old_adt_def_id, change) in ctor_changes {
+        changes.add_change(
+            ChangeType::ConstructorChanged { change },
+            old_adt_def_id,
+            None,
+        );
+    }
+
     for (o_def_id, n_def_id, output) in traits {
-        diff_traits(changes, id_mapping, tcx, o_def_id, n_def_id, output);
+        diff_traits(
+            changes,
+            id_mapping,
+            tcx,
+            options.doc_hidden_private,
+            o_def_id,
+            n_def_id,
+            output,
+        );
     }
 }
 
+/// Given a pair of possibly missing struct constructors, perform structural checks.
+///
+/// Constructors live in the value namespace and are only accessible if all fields of their
+/// struct are, so they can change independently of the struct itself. The change found is
+/// returned together with the old struct's `DefId`, provided the struct is present in both
+/// crate versions.
+fn diff_ctors(
+    id_mapping: &IdMapping,
+    tcx: TyCtxt,
+    old: Option<(DefId, Visibility)>,
+    new: Option<(DefId, Visibility)>,
+) -> Option<(DefId, CtorChange)> {
+    // whether a struct is present in a version, but lacks a constructor, which is always the
+    // case for type aliases, even if they expand to a tuple or unit struct
+    let ctor_missing = |adt_def_id: DefId| match tcx.def_kind(adt_def_id) {
+        DefKind::Struct => tcx
+            .adt_def(adt_def_id)
+            .non_enum_variant()
+            .ctor_def_id
+            .is_none(),
+        DefKind::TyAlias => true,
+        _ => false,
+    };
+
+    // the struct a constructor belongs to, the constructor's arity (if any), and whether the
+    // constructor is hidden, i.e. less visible than its struct
+    let ctor_info = |ctor_def_id: DefId| {
+        let adt_def_id = tcx.parent(ctor_def_id).unwrap();
+        let variant = tcx.adt_def(adt_def_id).non_enum_variant();
+        let arity = if variant.ctor_kind == CtorKind::Fn {
+            Some(variant.fields.len())
+        } else {
+            None
+        };
+        let hidden = tcx.visibility(adt_def_id) == Public && tcx.visibility(ctor_def_id) != Public;
+
+        (adt_def_id, arity, hidden)
+    };
+
+    let (old_adt_def_id, change) = match (old, new) {
+        (Some((o_def_id, o_vis)), Some((n_def_id, n_vis))) => {
+            let (old_adt_def_id, old_arity, old_hidden) = ctor_info(o_def_id);
+            let (new_adt_def_id, new_arity, new_hidden) = ctor_info(n_def_id);
+
+            if id_mapping.get_new_id(old_adt_def_id) != Some(new_adt_def_id) {
+                return None;
+            }
+
+            // visibility changes of the struct itself are recorded elsewhere
+            let change = if o_vis == Public && !old_hidden && new_hidden {
+                CtorChange::MadePrivate
+            } else if n_vis == Public && old_hidden && !new_hidden {
+                CtorChange::MadePublic
+            } else if let (true, Some(old), Some(new)) =
+                (o_vis == Public && n_vis == Public, old_arity, new_arity)
+            {
+                if old == new {
+                    return None;
+                }
+
+                CtorChange::ArityChanged { old, new }
+            } else {
+                return None;
+            };
+
+            (old_adt_def_id, change)
+        }
+        // path and kind changes of the struct itself are recorded elsewhere
+        (Some((o_def_id, o_vis)), None) if o_vis == Public => {
+            let (old_adt_def_id, _, _) = ctor_info(o_def_id);
+
+            match id_mapping.get_new_id(old_adt_def_id) {
+                Some(new_adt_def_id) if ctor_missing(new_adt_def_id) => {
+                    (old_adt_def_id, CtorChange::Removed)
+                }
+                _ => return None,
+            }
+        }
+        (None, Some((n_def_id, n_vis))) if n_vis == Public => {
+            let (new_adt_def_id, _, _) = ctor_info(n_def_id);
+
+            match id_mapping.get_old_id(new_adt_def_id) {
+                Some(old_adt_def_id) if ctor_missing(old_adt_def_id) => {
+                    (old_adt_def_id, CtorChange::Added)
+                }
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+
+    Some((old_adt_def_id, change))
+}
+
 /// Given two fn items, perform structural checks.
 fn diff_fn<'tcx>(changes: &mut ChangeSet, tcx: TyCtxt<'tcx>, old: Res, new: Res) {
     let old_def_id = old.def_id();
@@ -405,11 +819,28 @@ fn diff_method<'tcx>(changes: &mut ChangeSet, tcx: TyCtxt<'tcx>, old: AssocItem,
     );
 }
 
+/// Given a type alias, return the ADT of the given kind it expands to, if any.
+fn expand_alias(tcx: TyCtxt, kind: DefKind, alias_def_id: DefId) -> Option<Res> {
+    match (kind, tcx.type_of(alias_def_id).kind()) {
+        (DefKind::Struct, TyKind::Adt(def, _)) if def.is_struct() => Some(Def(kind, def.did)),
+        (DefKind::Union, TyKind::Adt(def, _)) if def.is_union() => Some(Def(kind, def.did)),
+        (DefKind::Enum, TyKind::Adt(def, _)) if def.is_enum() => Some(Def(kind, def.did)),
+        _ => None,
+    }
+}
+
 /// Given two ADT items, perform structural checks.
 ///
 /// This establishes the needed correspondence between non-toplevel items such as enum variants,
 /// struct- and enum fields etc.
-fn diff_adts(changes: &mut ChangeSet, id_mapping: &mut IdMapping, tcx: TyCtxt, old: Res, new: Res) {
+fn diff_adts(
+    changes: &mut ChangeSet,
+    id_mapping: &mut IdMapping,
+    tcx: TyCtxt,
+    doc_hidden_private: bool,
+    old: Res,
+    new: Res,
+) {
     use rustc_hir::def::DefKind::*;
 
     let old_def_id = old.def_id();
@@ -427,12 +858,28 @@ fn diff_adts(changes: &mut ChangeSet, id_mapping: &mut IdMapping, tcx: TyCtxt, o
     let mut variants = BTreeMap::new();
     let mut fields = BTreeMap::new();
 
-    for variant in &old_def.variants {
-        variants.entry(variant.ident.name).or_insert((None, None)).0 = Some(variant);
+    // the only variant of a struct or union is named after the item, which can differ if the
+    // item has been replaced by a type alias, so only enum variants are matched by name
+    let variant_key = |variant: &VariantDef| is_enum.then(|| variant.ident.name);
+
+    // `#[doc(hidden)]` variants and fields, like `__Nonexhaustive`, are treated as private if
+    // requested
+    let hidden = |def_id| doc_hidden_private && is_doc_hidden(tcx, def_id);
+    let is_public = |field: &FieldDef| field.vis == Public && !hidden(field.did);
+    let visible = |variant: &&VariantDef| !(is_enum && hidden(variant.def_id));
+
+    for variant in old_def.variants.iter().filter(visible) {
+        variants
+            .entry(variant_key(variant))
+            .or_insert((None, None))
+            .0 = Some(variant);
     }
 
-    for variant in &new_def.variants {
-        variants.entry(variant.ident.name).or_insert((None, None)).1 = Some(variant);
+    for variant in new_def.variants.iter().filter(visible) {
+        variants
+            .entry(variant_key(variant))
+            .or_insert((None, None))
+            .1 = Some(variant);
     }
 
     for items in variants.values() {
@@ -451,7 +898,7 @@ fn diff_adts(changes: &mut ChangeSet, id_mapping: &mut IdMapping, tcx: TyCtxt, o
 
                 for items2 in fields.values() {
                     if let Some(o) = items2.0 {
-                        let public = o.vis == Public;
+                        let public = is_public(o);
                         total_public &= public;
                         total_private &= !public;
                     }
@@ -468,18 +915,43 @@ fn diff_adts(changes: &mut ChangeSet, id_mapping: &mut IdMapping, tcx: TyCtxt, o
                     continue;
                 }
 
+                // struct constructors are handled in `diff_ctors`, but a variant's constructor
+                // can only lose its visibility by becoming `#[non_exhaustive]`.
+                if let (true, Some(o_ctor), Some(n_ctor)) =
+                    (is_enum, old.ctor_def_id, new.ctor_def_id)
+                {
+                    let o_pub = tcx.visibility(o_ctor) == Public;
+                    let n_pub = tcx.visibility(n_ctor) == Public;
+
+                    let change = if o_pub && !n_pub {
+                        Some(CtorChange::MadePrivate)
+                    } else if !o_pub && n_pub {
+                        Some(CtorChange::MadePublic)
+                    } else {
+                        None
+                    };
+
+                    if let Some(change) = change {
+                        changes.add_change(
+                            ChangeType::ConstructorChanged { change },
+                            old_def_id,
+                            Some(tcx.def_span(new.def_id)),
+                        );
+                    }
+                }
+
                 for items2 in fields.values() {
                     match *items2 {
                         (Some(o), Some(n)) => {
-                            if o.vis == Public && n.vis == Public {
+                            if is_public(o) && is_public(n) {
                                 id_mapping.add_subitem(old_def_id, o.did, n.did);
-                            } else if o.vis != Public && n.vis == Public {
+                            } else if !is_public(o) && is_public(n) {
                                 changes.add_change(
                                     ChangeType::ItemMadePublic,
                                     old_def_id,
                                     Some(tcx.def_span(n.did)),
                                 );
-                            } else if o.vis == Public && n.vis != Public {
+                            } else if is_public(o) && !is_public(n) {
                                 changes.add_change(
                                     ChangeType::ItemMadePrivate,
                                     old_def_id,
@@ -489,7 +961,7 @@ fn diff_adts(changes: &mut ChangeSet, id_mapping: &mut IdMapping, tcx: TyCtxt, o
                         }
                         (Some(o), None) => {
                             let c = ChangeType::VariantFieldRemoved {
-                                public: o.vis == Public,
+                                public: is_public(o),
                                 total_public,
                                 is_enum,
                             };
@@ -497,7 +969,7 @@ fn diff_adts(changes: &mut ChangeSet, id_mapping: &mut IdMapping, tcx: TyCtxt, o
                         }
                         (None, Some(n)) => {
                             let c = ChangeType::VariantFieldAdded {
-                                public: n.vis == Public,
+                                public: is_public(n),
                                 total_public,
                                 is_enum,
                             };
@@ -562,6 +1034,7 @@ fn diff_traits<'tcx>(
     changes: &mut ChangeSet,
     id_mapping: &mut IdMapping,
     tcx: TyCtxt<'tcx>,
+    doc_hidden_private: bool,
     old: DefId,
     new: DefId,
     output: bool,
@@ -619,6 +1092,10 @@ fn diff_traits<'tcx>(
         // tcx.describe_def(*new_def_id).map(|d| (d, item));
     }
 
+    // `#[doc(hidden)]` items, like methods only meant to be called by the crate's macros, are
+    // treated as private if requested
+    let hidden = |def_id| doc_hidden_private && is_doc_hidden(tcx, def_id);
+
     for (name, item_pair) in &items {
         match *item_pair {
             (Some(old_item), Some(new_item)) => {
@@ -634,25 +1111,71 @@ fn diff_traits<'tcx>(
                     *name,
                     tcx.def_span(old_def_id),
                     tcx.def_span(new_def_id),
-                    output,
+                    output && !(hidden(old_def_id) && hidden(new_def_id)),
                 );
 
                 diff_generics(changes, id_mapping, tcx, true, old_def_id, new_def_id);
                 diff_method(changes, tcx, *old_item, *new_item);
             }
             (Some(old_item), None) => {
-                let change_type = ChangeType::TraitItemRemoved {
-                    defaulted: old_item.defaultness.has_value(),
-                };
-                changes.add_change(change_type, old, Some(tcx.def_span(old_item.def_id)));
-                id_mapping.add_non_mapped(old_item.def_id);
+                // the item might have been moved to a (possibly new) supertrait, in which case
+                // it is still available to callers
+                let moved_item = supertrait_def_ids(tcx, new)
+                    .skip(1)
+                    .filter(|super_def_id| id_mapping.in_new_crate(*super_def_id))
+                    .find_map(|super_def_id| {
+                        tcx.associated_items(super_def_id).find_by_name_and_kind(
+                            tcx,
+                            old_item.ident,
+                            old_item.kind,
+                            super_def_id,
+                        )
+                    })
+                    .filter(|new_item| !id_mapping.contains_new_id(new_item.def_id));
+
+                // whether the move is reported as such depends on the compatibility of the
+                // item's signature, so it's only reported once types have been compared, see
+                // `diff_moved_trait_items`
+                if let Some(new_item) = moved_item {
+                    let old_def_id = old_item.def_id;
+                    let new_def_id = new_item.def_id;
+                    let old_res = Res::Def(old_item.kind.as_def_kind(), old_def_id);
+                    let new_res = Res::Def(new_item.kind.as_def_kind(), new_def_id);
+
+                    id_mapping.add_trait_item(old_res, new_res, old);
+                    id_mapping.add_moved_trait_item(old_def_id, old_sealed);
+                    changes.new_change(
+                        old_def_id,
+                        new_def_id,
+                        *name,
+                        tcx.def_span(old_def_id),
+                        tcx.def_span(new_def_id),
+                        output && !(hidden(old_def_id) && hidden(new_def_id)),
+                    );
+
+                    diff_generics(changes, id_mapping, tcx, true, old_def_id, new_def_id);
+                    diff_method(changes, tcx, *old_item, *new_item);
+                } else {
+                    if !hidden(old_item.def_id) {
+                        let change_type = ChangeType::TraitItemRemoved {
+                            defaulted: old_item.defaultness.has_value(),
+                        };
+                        changes.add_change(change_type, old, Some(tcx.def_span(old_item.def_id)));
+                    }
+                    id_mapping.add_non_mapped(old_item.def_id);
+                }
             }
             (None, Some(new_item)) => {
-                let change_type = ChangeType::TraitItemAdded {
-                    defaulted: new_item.defaultness.has_value(),
-                    sealed_trait: old_sealed,
-                };
-                changes.add_change(change_type, old, Some(tcx.def_span(new_item.def_id)));
+                let defaulted = new_item.defaultness.has_value();
+
+                // hidden items still have to be implemented if they lack a default
+                if !(hidden(new_item.def_id) && defaulted) {
+                    let change_type = ChangeType::TraitItemAdded {
+                        defaulted,
+                        sealed_trait: old_sealed,
+                    };
+                    changes.add_change(change_type, old, Some(tcx.def_span(new_item.def_id)));
+                }
                 id_mapping.add_non_mapped(new_item.def_id);
             }
             (None, None) => unreachable!(),
@@ -660,6 +1183,33 @@ fn diff_traits<'tcx>(
     }
 }
 
+/// Get the names of a function's late-bound lifetimes, which don't appear in its generics.
+fn get_late_bound_regions(tcx: TyCtxt, def_id: DefId) -> Vec<Symbol> {
+    use rustc_middle::ty::{BoundRegionKind, BoundVariableKind};
+
+    tcx.fn_sig(def_id)
+        .bound_vars()
+        .iter()
+        .filter_map(|var| match var {
+            BoundVariableKind::Region(BoundRegionKind::BrNamed(_, name)) => Some(name),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Check whether some early-bound lifetimes of a function became late-bound in the target.
+fn regions_became_late_bound(tcx: TyCtxt, orig_def_id: DefId, target_def_id: DefId) -> bool {
+    if !matches!(tcx.def_kind(target_def_id), DefKind::Fn | DefKind::AssocFn) {
+        return false;
+    }
+
+    let target_late = get_late_bound_regions(tcx, target_def_id);
+
+    tcx.generics_of(orig_def_id).params.iter().any(|param| {
+        matches!(param.kind, GenericParamDefKind::Lifetime) && target_late.contains(&param.name)
+    })
+}
+
 /// Given two items, compare their type and region parameter sets.
 fn diff_generics(
     changes: &mut ChangeSet,
@@ -690,16 +1240,6 @@ fn diff_generics(
         }
     }
 
-    // guarantee that the return value's kind is `GenericParamDefKind::Lifetime`
-    fn get_region_from_params(gen: &Generics, idx: usize) -> Option<&GenericParamDef> {
-        let param = gen.params.get(idx)?;
-
-        match param.kind {
-            GenericParamDefKind::Lifetime => Some(param),
-            _ => None,
-        }
-    }
-
     // guarantee that the return value's kind is `GenericParamDefKind::Type`
     fn get_type_from_params(gen: &Generics, idx: usize) -> Option<&GenericParamDef> {
         let param = &gen.params.get(idx)?;
@@ -710,6 +1250,113 @@ fn diff_generics(
         }
     }
 
+    // get the generic parameters that can be specified using the turbofish syntax, along with
+    // the number of `impl Trait` arguments
+    fn get_explicit_params(gen: &Generics) -> (Vec<&GenericParamDef>, usize) {
+        let mut explicit = Vec::new();
+        let mut synthetic = 0;
+
+        for param in &gen.params {
+            match param.kind {
+                GenericParamDefKind::Type {
+                    synthetic: Some(_), ..
+                } => synthetic += 1,
+                GenericParamDefKind::Type { .. } | GenericParamDefKind::Const { .. } => {
+                    explicit.push(param)
+                }
+                GenericParamDefKind::Lifetime => (),
+            }
+        }
+
+        (explicit, synthetic)
+    }
+
+    // get the role of a generic parameter of a function, i.e. its kind and the positions at
+    // which it occurs in the function's signature, which is independent of its name
+    fn param_role(tcx: TyCtxt, fn_def_id: DefId, param: &GenericParamDef) -> (bool, Vec<usize>) {
+        use rustc_middle::ty::{subst::GenericArgKind, ConstKind};
+
+        let is_const = matches!(param.kind, GenericParamDefKind::Const { .. });
+        let sig = tcx.fn_sig(fn_def_id).skip_binder();
+        let occurrences = sig
+            .inputs_and_output
+            .iter()
+            .flat_map(|ty| ty.walk())
+            .enumerate()
+            .filter(|(_, arg)| match arg.unpack() {
+                GenericArgKind::Type(ty) => {
+                    matches!(*ty.kind(), TyKind::Param(p) if p.index == param.index)
+                }
+                GenericArgKind::Const(c) => {
+                    matches!(c.val, ConstKind::Param(p) if p.index == param.index)
+                }
+                GenericArgKind::Lifetime(_) => false,
+            })
+            .map(|(i, _)| i)
+            .collect();
+
+        (is_const, occurrences)
+    }
+
+    // determine whether turbofish calls to a function break, which is only possible if the
+    // old version had explicit generic parameters at all and no `impl Trait` arguments, as
+    // explicit generic arguments are rejected for functions having those (E0632)
+    //
+    // parameters are compared by position and role rather than by name, so renaming them is
+    // fine, while a parameter taking over the role of another one at a different position is
+    // a reordering, whatever the parameters are called
+    fn diff_turbofish(
+        tcx: TyCtxt,
+        old: DefId,
+        new: DefId,
+        old_gen: &Generics,
+        new_gen: &Generics,
+    ) -> Option<TurbofishChange> {
+        let (old_explicit, old_synthetic) = get_explicit_params(old_gen);
+        let (new_explicit, new_synthetic) = get_explicit_params(new_gen);
+
+        if old_explicit.is_empty() || old_synthetic > 0 {
+            return None;
+        } else if new_synthetic > 0 {
+            return Some(TurbofishChange::ImplTraitIntroduced);
+        } else if old_explicit.len() != new_explicit.len() {
+            return Some(TurbofishChange::ArityChanged {
+                old: old_explicit.len(),
+                new: new_explicit.len(),
+            });
+        }
+
+        let old_roles: Vec<_> = old_explicit
+            .iter()
+            .map(|param| param_role(tcx, old, param))
+            .collect();
+        let new_roles: Vec<_> = new_explicit
+            .iter()
+            .map(|param| param_role(tcx, new, param))
+            .collect();
+
+        // a parameter whose role moved to another position, which can only be told if the role
+        // is unique, and didn't change along with the rest of the signature
+        let moved = new_roles.iter().enumerate().any(|(new_pos, role)| {
+            let mut old_pos = old_roles
+                .iter()
+                .enumerate()
+                .filter(|(_, old_role)| *old_role == role)
+                .map(|(old_pos, _)| old_pos);
+
+            match (old_pos.next(), old_pos.next()) {
+                (Some(old_pos), None) => old_pos != new_pos,
+                _ => false,
+            }
+        });
+
+        if moved {
+            Some(TurbofishChange::Reordered)
+        } else {
+            None
+        }
+    }
+
     debug!("diff_generics: old: {:?}, new: {:?}", old, new);
 
     let mut found = Vec::new();
@@ -717,6 +1364,12 @@ fn diff_generics(
     let old_gen = tcx.generics_of(old);
     let new_gen = tcx.generics_of(new);
 
+    let turbofish = if is_fn {
+        diff_turbofish(tcx, old, new, old_gen, new_gen)
+    } else {
+        None
+    };
+
     let old_var = tcx.variances_of(old);
     let new_var = tcx.variances_of(new);
 
@@ -739,14 +1392,61 @@ fn diff_generics(
         old_count.lifetimes, new_count.lifetimes
     );
 
-    for i in 0..max(old_count.lifetimes, new_count.lifetimes) {
-        match (
-            get_region_from_params(old_gen, i + self_add),
-            get_region_from_params(new_gen, i + self_add),
-        ) {
+    // a function's lifetimes can switch between being early- and late-bound, in which case
+    // they are not matched positionally, but reported separately
+    let (old_late, new_late) =
+        if is_fn && matches!(tcx.def_kind(old), DefKind::Fn | DefKind::AssocFn) {
+            (
+                get_late_bound_regions(tcx, old),
+                get_late_bound_regions(tcx, new),
+            )
+        } else {
+            (Vec::new(), Vec::new())
+        };
+
+    let old_regions: Vec<_> = old_gen
+        .params
+        .iter()
+        .filter(|param| matches!(param.kind, GenericParamDefKind::Lifetime))
+        .collect();
+    let new_regions: Vec<_> = new_gen
+        .params
+        .iter()
+        .filter(|param| matches!(param.kind, GenericParamDefKind::Lifetime))
+        .collect();
+
+    for region in old_regions.iter().filter(|r| new_late.contains(&r.name)) {
+        found.push(ChangeType::RegionBindingChanged {
+            name: region.name,
+            now_late_bound: true,
+        });
+    }
+
+    for region in new_regions.iter().filter(|r| old_late.contains(&r.name)) {
+        found.push(ChangeType::RegionBindingChanged {
+            name: region.name,
+            now_late_bound: false,
+        });
+    }
+
+    let old_regions: Vec<_> = old_regions
+        .into_iter()
+        .filter(|r| !new_late.contains(&r.name))
+        .collect();
+    let new_regions: Vec<_> = new_regions
+        .into_iter()
+        .filter(|r| !old_late.contains(&r.name))
+        .collect();
+
+    for i in 0..max(old_regions.len(), new_regions.len()) {
+        match (old_regions.get(i), new_regions.get(i)) {
             (Some(old_region), Some(new_region)) => {
-                // type aliases don't have inferred variance, so we have to ignore that.
-                if let (Some(old_var), Some(new_var)) = (old_var.get(i), new_var.get(i)) {
+                // type aliases don't have inferred variance, so we have to ignore that. the
+                // regions are looked up by their own index, as some of them might be skipped.
+                if let (Some(old_var), Some(new_var)) = (
+                    old_var.get(old_region.index as usize),
+                    new_var.get(new_region.index as usize),
+                ) {
                     if let Some(t) = diff_variance(*old_var, *new_var) {
                         found.push(t)
                     };
@@ -833,9 +1533,13 @@ fn diff_generics(
                     _ => unreachable!(),
                 };
 
-                found.push(ChangeType::TypeParameterAdded {
-                    defaulted: new_default || is_fn,
-                });
+                // type parameters added to functions are inferred, unless they break turbofish
+                // calls, which are reported on their own
+                if turbofish.is_none() {
+                    found.push(ChangeType::TypeParameterAdded {
+                        defaulted: new_default || is_fn,
+                    });
+                }
                 id_mapping.add_type_param(new_type);
                 id_mapping.add_non_mapped(new_type.def_id);
             }
@@ -843,6 +1547,10 @@ fn diff_generics(
         }
     }
 
+    if let Some(change) = turbofish {
+        found.push(ChangeType::TurbofishBroken { change });
+    }
+
     for change_type in found.drain(..) {
         changes.add_change(change_type, old, None);
     }
@@ -888,8 +1596,13 @@ fn diff_types<'tcx>(
         }
         // functions and methods require us to compare their signatures, not types
         Def(Fn, _) | Def(AssocFn, _) => {
-            let old_fn_sig = tcx.type_of(old_def_id).fn_sig(tcx);
-            let new_fn_sig = tcx.type_of(new_def_id).fn_sig(tcx);
+            let (old_fn_sig, new_fn_sig) =
+                fn_sigs_for_comparison(changes, id_mapping, tcx, old_def_id, new_def_id);
+
+            // lifetimes that became late-bound are bound in the old signature as well, since
+            // the change has already been recorded and only generalizes the function
+            let old_fn_sig =
+                bind_early_regions(tcx, old_fn_sig, &get_late_bound_regions(tcx, new_def_id));
 
             cmp_types(
                 changes,
@@ -903,10 +1616,18 @@ fn diff_types<'tcx>(
         }
         // ADTs' types are compared field-wise
         Def(Struct, _) | Def(Enum, _) | Def(Union, _) => {
+            // if the ADT has been replaced by a type alias, its fields have been matched with
+            // those of the alias' expansion, so their types need to be substituted accordingly
+            let alias_substs = match (new, tcx.type_of(new_def_id).kind()) {
+                (Def(TyAlias, _), TyKind::Adt(_, substs)) => Some(*substs),
+                _ => None,
+            };
+
             if let Some(children) = id_mapping.children_of(old_def_id) {
                 for (o_def_id, n_def_id) in children {
                     let o_ty = tcx.type_of(o_def_id);
                     let n_ty = tcx.type_of(n_def_id);
+                    let n_ty = alias_substs.map_or(n_ty, |substs| n_ty.subst(tcx, substs));
 
                     cmp_types(changes, id_mapping, tcx, old_def_id, new_def_id, o_ty, n_ty);
                 }
@@ -920,6 +1641,301 @@ fn diff_types<'tcx>(
     }
 }
 
+/// Report the trait items moved to a supertrait, once their types have been compared.
+///
+/// A moved item is only available as before if its signature is compatible, otherwise it is
+/// reported as removed from its trait. The impact of a move on implementors is reported
+/// separately, unless the trait is sealed, and covers the supertrait bound added for it.
+fn diff_moved_trait_items(changes: &mut ChangeSet, id_mapping: &IdMapping, tcx: TyCtxt) {
+    use rustc_middle::ty::PredicateKind;
+
+    for (old_def_id, sealed_trait) in id_mapping.moved_trait_items() {
+        let old_trait = id_mapping.get_trait_def(old_def_id).unwrap();
+        let new_def_id = id_mapping.get_new_id(old_def_id).unwrap();
+
+        // an incompatible item is reported through its own changes already, reporting it as
+        // removed from the trait as well would count it twice
+        if changes.item_breaking(old_def_id) {
+            continue;
+        }
+
+        let supertrait = tcx.parent(new_def_id).unwrap();
+        let supertrait_path = tcx.def_path_str(supertrait);
+        let span = Some(tcx.def_span(new_def_id));
+
+        let change_type = ChangeType::TraitItemMovedToSupertrait {
+            supertrait: supertrait_path.clone(),
+        };
+        changes.add_change(change_type, old_trait, span);
+
+        if !sealed_trait {
+            let change_type = ChangeType::TraitItemImplMovedToSupertrait {
+                supertrait: supertrait_path,
+            };
+            changes.add_change(change_type, old_trait, span);
+        }
+
+        // the bounds on all supertraits the item is now inherited through are implied
+        let new_trait = id_mapping.get_new_id(old_trait).unwrap();
+        let implied: Vec<_> = supertrait_def_ids(tcx, new_trait)
+            .skip(1)
+            .filter(|&did| supertrait_def_ids(tcx, did).any(|did| did == supertrait))
+            .collect();
+
+        changes.retain_changes(old_trait, |change| match change {
+            ChangeType::BoundsTightened { pred } => match pred.kind().skip_binder() {
+                PredicateKind::Trait(pred, _) => {
+                    !(implied.contains(&pred.def_id()) && pred.self_ty().is_param(0))
+                }
+                _ => true,
+            },
+            _ => true,
+        });
+    }
+}
+
+/// Get the signatures of two functions, prepared for type comparison.
+///
+/// If both functions return futures, be it as `async fn`s or by returning `impl Future`, the
+/// opaque return types can't be compared directly. They are replaced by the futures' output
+/// types, and the properties of the futures visible to users, i.e. whether they are `Send` and
+/// which parameters' lifetimes they capture, are compared separately.
+fn fn_sigs_for_comparison<'tcx>(
+    changes: &mut ChangeSet<'tcx>,
+    id_mapping: &IdMapping,
+    tcx: TyCtxt<'tcx>,
+    old_def_id: DefId,
+    new_def_id: DefId,
+) -> (PolyFnSig<'tcx>, PolyFnSig<'tcx>) {
+    let old_sig = tcx.type_of(old_def_id).fn_sig(tcx);
+    let new_sig = tcx.type_of(new_def_id).fn_sig(tcx);
+
+    let (old_output, new_output) = match (
+        future_output(tcx, old_sig.output().skip_binder()),
+        future_output(tcx, new_sig.output().skip_binder()),
+    ) {
+        (Some(old_output), Some(new_output)) => (old_output, new_output),
+        _ => return (old_sig, new_sig),
+    };
+
+    if let Some(send_def_id) = tcx.lang_items().send_trait() {
+        let old_send = future_meets_bound(tcx, old_def_id, old_sig, send_def_id);
+        let new_send = future_meets_bound(tcx, new_def_id, new_sig, send_def_id);
+
+        if old_send != new_send {
+            let change_type = ChangeType::FutureSendChanged { now_send: new_send };
+            changes.add_change(change_type, old_def_id, None);
+        }
+    }
+
+    // the remaining bounds of either future are checked against the other one, which takes
+    // auto traits leaking through the opaque type into account
+    for old_bound in future_bounds(tcx, old_sig) {
+        if let Some(new_bound) = id_mapping.get_new_id(old_bound) {
+            if !future_meets_bound(tcx, new_def_id, new_sig, new_bound) {
+                let change_type = ChangeType::FutureBoundChanged {
+                    bound: tcx.def_path_str(new_bound),
+                    now_bound: false,
+                };
+                changes.add_change(change_type, old_def_id, None);
+            }
+        }
+    }
+
+    for new_bound in future_bounds(tcx, new_sig) {
+        if let Some(old_bound) = id_mapping.get_old_id(new_bound) {
+            if !future_meets_bound(tcx, old_def_id, old_sig, old_bound) {
+                let change_type = ChangeType::FutureBoundChanged {
+                    bound: tcx.def_path_str(new_bound),
+                    now_bound: true,
+                };
+                changes.add_change(change_type, old_def_id, None);
+            }
+        }
+    }
+
+    let old_captures = future_captures(tcx, old_sig);
+    let new_captures = future_captures(tcx, new_sig);
+
+    // parameters are matched positionally, so changes in their number are left to the
+    // signature comparison
+    if old_captures.len() == new_captures.len() {
+        let arg_names = tcx.fn_arg_names(new_def_id);
+
+        for (i, (old_captured, new_captured)) in
+            old_captures.into_iter().zip(new_captures).enumerate()
+        {
+            if old_captured != new_captured {
+                let param = arg_names.get(i).map_or(kw::Underscore, |ident| ident.name);
+                let change_type = ChangeType::FutureCaptureChanged {
+                    param,
+                    now_captured: new_captured,
+                };
+
+                changes.add_change(change_type, old_def_id, None);
+            }
+        }
+    }
+
+    let replace_output = |sig: PolyFnSig<'tcx>, output: Ty<'tcx>| {
+        sig.map_bound(|sig| {
+            let inputs_and_output = sig.inputs().iter().copied().chain(Some(output));
+
+            FnSig {
+                inputs_and_output: tcx.mk_type_list(inputs_and_output),
+                ..sig
+            }
+        })
+    };
+
+    (
+        replace_output(old_sig, old_output),
+        replace_output(new_sig, new_output),
+    )
+}
+
+/// Get the output type of a future, if the given type is an opaque `impl Future` type.
+fn future_output<'tcx>(tcx: TyCtxt<'tcx>, ty: Ty<'tcx>) -> Option<Ty<'tcx>> {
+    use rustc_middle::ty::PredicateKind;
+
+    let (opaque_def_id, substs) = match *ty.kind() {
+        TyKind::Opaque(def_id, substs) => (def_id, substs),
+        _ => return None,
+    };
+    let future_def_id = tcx.lang_items().future_trait()?;
+
+    tcx.explicit_item_bounds(opaque_def_id)
+        .iter()
+        .find_map(|(pred, _)| match pred.kind().skip_binder() {
+            PredicateKind::Projection(proj)
+                if proj.projection_ty.trait_def_id(tcx) == future_def_id =>
+            {
+                Some(proj.ty.subst(tcx, substs))
+            }
+            _ => None,
+        })
+}
+
+/// Get the traits bounding the opaque `impl Future` type returned by a function, apart from
+/// `Future` itself, `Sized`, and `Send`, which is compared on its own.
+///
+/// Only traits without parameters of their own are considered, like `Unpin` or `Sync`.
+fn future_bounds<'tcx>(tcx: TyCtxt<'tcx>, sig: PolyFnSig<'tcx>) -> Vec<DefId> {
+    use rustc_middle::ty::PredicateKind;
+
+    let opaque_def_id = match *sig.skip_binder().output().kind() {
+        TyKind::Opaque(def_id, _) => def_id,
+        _ => return Vec::new(),
+    };
+    let lang_items = tcx.lang_items();
+    let skipped = [
+        lang_items.future_trait(),
+        lang_items.sized_trait(),
+        lang_items.send_trait(),
+    ];
+
+    tcx.explicit_item_bounds(opaque_def_id)
+        .iter()
+        .filter_map(|(pred, _)| match pred.kind().skip_binder() {
+            PredicateKind::Trait(pred, _)
+                if pred.trait_ref.substs.len() == 1 && !skipped.contains(&Some(pred.def_id())) =>
+            {
+                Some(pred.def_id())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Check whether the future returned by a function implements the given trait.
+fn future_meets_bound<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    fn_def_id: DefId,
+    sig: PolyFnSig<'tcx>,
+    trait_def_id: DefId,
+) -> bool {
+    use rustc_trait_selection::traits::type_known_to_meet_bound_modulo_regions;
+
+    let output = tcx.erase_late_bound_regions(sig.output());
+    let param_env = tcx.param_env(fn_def_id);
+
+    tcx.infer_ctxt().enter(|infcx| {
+        type_known_to_meet_bound_modulo_regions(
+            &infcx,
+            param_env,
+            output,
+            trait_def_id,
+            tcx.def_span(fn_def_id),
+        )
+    })
+}
+
+/// Determine which parameters of a function have their lifetimes captured by the opaque type it
+/// returns.
+fn future_captures<'tcx>(tcx: TyCtxt<'tcx>, sig: PolyFnSig<'tcx>) -> Vec<bool> {
+    use rustc_middle::ty::subst::GenericArgKind;
+
+    let sig = sig.skip_binder();
+    let captured: Vec<_> = match *sig.output().kind() {
+        TyKind::Opaque(opaque_def_id, substs) => {
+            let parent_count = tcx.generics_of(opaque_def_id).parent_count;
+
+            substs[parent_count..]
+                .iter()
+                .filter_map(|arg| match arg.unpack() {
+                    GenericArgKind::Lifetime(region) => Some(region),
+                    _ => None,
+                })
+                .collect()
+        }
+        _ => Vec::new(),
+    };
+
+    sig.inputs()
+        .iter()
+        .map(|input| {
+            input.walk().any(|arg| match arg.unpack() {
+                GenericArgKind::Lifetime(region) => captured.contains(&region),
+                _ => false,
+            })
+        })
+        .collect()
+}
+
+/// Turn the early-bound lifetimes of a function signature with the given names into
+/// late-bound ones.
+fn bind_early_regions<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    sig: PolyFnSig<'tcx>,
+    names: &[Symbol],
+) -> PolyFnSig<'tcx> {
+    use rustc_middle::ty::{
+        BoundRegion, BoundRegionKind::BrNamed, BoundVar, BoundVariableKind, RegionKind,
+    };
+
+    let mut bound_vars: Vec<_> = sig.bound_vars().iter().collect();
+    let mut new_vars = HashMap::new();
+
+    let inner = tcx.fold_regions(
+        sig.skip_binder(),
+        &mut false,
+        |region, depth| match *region {
+            RegionKind::ReEarlyBound(early) if names.contains(&early.name) => {
+                let kind = BrNamed(early.def_id, early.name);
+                let var = *new_vars.entry(early.name).or_insert_with(|| {
+                    bound_vars.push(BoundVariableKind::Region(kind));
+                    BoundVar::from_usize(bound_vars.len() - 1)
+                });
+
+                tcx.mk_region(RegionKind::ReLateBound(depth, BoundRegion { var, kind }))
+            }
+            _ => region,
+        },
+    );
+
+    Binder::bind_with_vars(inner, tcx.mk_bound_variable_kinds(bound_vars.into_iter()))
+}
+
 /// Compare two types and their trait bounds, possibly registering the resulting change.
 fn cmp_types<'tcx>(
     changes: &mut ChangeSet<'tcx>,
@@ -935,7 +1951,7 @@ fn cmp_types<'tcx>(
         orig_def_id, target_def_id, orig, target
     );
 
-    tcx.infer_ctxt().enter(|infcx| {
+    let type_error = tcx.infer_ctxt().enter(|infcx| {
         let compcx = TypeComparisonContext::target_new(&infcx, id_mapping, false);
 
         let orig_substs = InternalSubsts::identity_for_item(infcx.tcx, target_def_id);
@@ -955,11 +1971,13 @@ fn cmp_types<'tcx>(
             .param_env(target_def_id)
             .subst(infcx.tcx, target_substs);
 
-        if let Some(err) =
-            compcx.check_type_error(tcx, target_def_id, target_param_env, orig, target)
-        {
-            changes.add_change(ChangeType::TypeChanged { error: err }, orig_def_id, None);
-        } else {
+        let error = compcx.check_type_error(tcx, target_def_id, target_param_env, orig, target);
+
+        // the original bounds can't be instantiated with the target's parameters if some of
+        // them became late-bound lifetimes, which can't be bounded anyway
+        let substs_compatible = !regions_became_late_bound(tcx, orig_def_id, target_def_id);
+
+        if error.is_none() && substs_compatible {
             // check the bounds if no type error has been found
             compcx.check_bounds_bidirectional(
                 changes,
@@ -970,7 +1988,93 @@ fn cmp_types<'tcx>(
                 target_substs,
             );
         }
+
+        error
+    });
+
+    let error = if let Some(error) = type_error {
+        error
+    } else {
+        return;
+    };
+
+    if !(orig.is_fn_ptr()
+        && cmp_fn_sigs(
+            changes,
+            id_mapping,
+            tcx,
+            orig_def_id,
+            target_def_id,
+            orig,
+            target,
+            None,
+        ))
+    {
+        changes.add_change(ChangeType::TypeChanged { error }, orig_def_id, None);
+    }
+}
+
+/// Narrow down a type error between two fn pointer types to their parameters and return types.
+///
+/// The changes found are registered, and `false` is returned if none could be found. For
+/// methods, the `DefId`s of the impls containing them can be passed, in which case their `Self`
+/// types are unified first.
+#[allow(clippy::too_many_arguments)]
+fn cmp_fn_sigs<'tcx>(
+    changes: &mut ChangeSet<'tcx>,
+    id_mapping: &IdMapping,
+    tcx: TyCtxt<'tcx>,
+    orig_def_id: DefId,
+    target_def_id: DefId,
+    orig: Ty<'tcx>,
+    target: Ty<'tcx>,
+    impls: Option<(DefId, DefId)>,
+) -> bool {
+    let fn_changes = tcx.infer_ctxt().enter(|infcx| {
+        let compcx = TypeComparisonContext::target_new(&infcx, id_mapping, false);
+
+        let orig = compcx.forward_trans.translate_item_type(orig_def_id, orig);
+        let target_substs = compcx.compute_target_infer_substs(target_def_id);
+        let target_param_env = infcx
+            .tcx
+            .param_env(target_def_id)
+            .subst(infcx.tcx, target_substs);
+
+        let self_tys = impls.map(|(orig_impl_def_id, target_impl_def_id)| {
+            let orig_self = compcx
+                .forward_trans
+                .translate_item_type(orig_impl_def_id, infcx.tcx.type_of(orig_impl_def_id));
+            let target_self = infcx
+                .tcx
+                .type_of(target_impl_def_id)
+                .subst(infcx.tcx, target_substs);
+
+            (orig_self, target_self)
+        });
+
+        compcx.check_fn_sig_errors(
+            tcx,
+            target_param_env,
+            self_tys,
+            orig.fn_sig(infcx.tcx),
+            target.fn_sig(infcx.tcx),
+            target_substs,
+        )
     });
+
+    if fn_changes.is_empty() {
+        return false;
+    }
+
+    for (change_type, index) in fn_changes {
+        let span = index
+            .and_then(|i| tcx.fn_arg_names(target_def_id).get(i))
+            .map(|ident| ident.span)
+            .filter(|span| !span.is_dummy());
+        changes.add_change(change_type, orig_def_id, span);
+    }
+
+    true
 }
 
 /// Compare the trait bounds of two items, possibly registering the resulting change.
@@ -1011,6 +2115,7 @@ fn diff_inherent_impls<'tcx>(
     changes: &mut ChangeSet<'tcx>,
     id_mapping: &IdMapping,
     tcx: TyCtxt<'tcx>,
+    doc_hidden_private: bool,
 ) {
     debug!("diffing inherent impls");
 
@@ -1019,13 +2124,24 @@ fn diff_inherent_impls<'tcx>(
 
     for (orig_item, orig_impls) in id_mapping.inherent_impls() {
         // determine where the item comes from
-        let (forward_trans, err_type) = if id_mapping.in_old_crate(orig_item.parent_def_id) {
-            (&to_new, ChangeType::AssociatedItemRemoved)
-        } else if id_mapping.in_new_crate(orig_item.parent_def_id) {
-            (&to_old, ChangeType::AssociatedItemAdded)
-        } else {
-            unreachable!()
-        };
+        let (forward_trans, backward_trans, target_crate, err_type) =
+            if id_mapping.in_old_crate(orig_item.parent_def_id) {
+                (
+                    &to_new,
+                    &to_old,
+                    id_mapping.get_new_crate(),
+                    ChangeType::AssociatedItemRemoved,
+                )
+            } else if id_mapping.in_new_crate(orig_item.parent_def_id) {
+                (
+                    &to_old,
+                    &to_new,
+                    id_mapping.get_old_crate(),
+                    ChangeType::AssociatedItemAdded,
+                )
+            } else {
+                unreachable!()
+            };
 
         // determine item visibility
         let parent_output = changes.get_output(orig_item.parent_def_id);
@@ -1041,17 +2157,36 @@ fn diff_inherent_impls<'tcx>(
                 orig_item.name,
                 item_span,
                 item_span,
-                parent_output && orig_assoc_item.vis == Public,
+                parent_output
+                    && orig_assoc_item.vis == Public
+                    && !(doc_hidden_private && is_doc_hidden(tcx, orig_item_def_id)),
             );
 
             // ... determine the set of target impls that serve as candidates
-            let target_impls = if let Some(impls) = forward_trans
-                .translate_inherent_entry(orig_item)
-                .and_then(|item| id_mapping.get_inherent_impls(&item))
+            let target_item = forward_trans.translate_inherent_entry(orig_item);
+            let target_impls = if let Some(impls) = target_item
+                .as_ref()
+                .and_then(|item| id_mapping.get_inherent_impls(item))
             {
                 impls
             } else {
-                changes.add_change(err_type.clone(), orig_item_def_id, None);
+                // the item might have been moved from or to a trait impl for the same type
+                let trait_def_id = target_item
+                    .and_then(|item| find_moved_method(tcx, backward_trans, target_crate, &item));
+
+                let change_type = match (trait_def_id, &err_type) {
+                    (Some(did), ChangeType::AssociatedItemRemoved) => {
+                        ChangeType::MethodMovedToTrait {
+                            trait_path: tcx.def_path_str(did),
+                        }
+                    }
+                    (Some(did), _) => ChangeType::MethodMovedFromTrait {
+                        trait_path: tcx.def_path_str(did),
+                    },
+                    (None, _) => err_type.clone(),
+                };
+
+                changes.add_change(change_type, orig_item_def_id, None);
                 continue;
             };
 
@@ -1131,16 +2266,135 @@ fn is_impl_trait_public<'tcx>(tcx: TyCtxt<'tcx>, impl_def_id: DefId) -> bool {
     is_public
 }
 
+// Below functions are used to check for changes of dependencies appearing in public signatures.
+
+/// Given two items, check whether the crates their signatures refer to have changed.
+///
+/// Different versions of the same crate are told apart by being distinct crates that share a
+/// name. If the version numbers are known, distinct crates with the same version are considered
+/// unchanged. Crates whose items are re-exported are compared item by item instead, and are
+/// thus skipped here.
+fn diff_dependencies<'tcx>(
+    changes: &mut ChangeSet<'tcx>,
+    id_mapping: &IdMapping,
+    tcx: TyCtxt<'tcx>,
+    dependency_versions: &DependencyVersions,
+    old: Res,
+    new: Res,
+) {
+    let old_def_id = old.def_id();
+
+    if !id_mapping.in_old_crate(old_def_id) {
+        return;
+    }
+
+    let old_crates = signature_crates(tcx, old);
+    let new_crates = signature_crates(tcx, new);
+    let mut dependency_distinct = false;
+
+    for (name, old_crate) in old_crates {
+        match new_crates.get(&name) {
+            Some(&new_crate)
+                if new_crate != old_crate
+                    && !id_mapping.in_old_crate(old_crate.as_def_id())
+                    && !id_mapping.in_new_crate(new_crate.as_def_id()) =>
+            {
+                dependency_distinct = true;
+
+                // both versions of the crate are always built against their own copy of a
+                // dependency, so the dependency only changed if the versions differ
+                let name_str = name.as_str();
+                let old_version = dependency_versions.get_old(&name_str);
+                let new_version = dependency_versions.get_new(&name_str);
+
+                if old_version.is_some() && old_version == new_version {
+                    continue;
+                }
+
+                let change_type = ChangeType::PublicDependencyChanged {
+                    krate: name,
+                    old_version: old_version.cloned(),
+                    new_version: new_version.cloned(),
+                };
+
+                changes.add_change(change_type, old_def_id, None);
+            }
+            _ => (),
+        }
+    }
+
+    // mismatches between the two copies' types and traits are explained by the change above,
+    // or don't exist at all if the dependency's version stayed the same
+    if dependency_distinct {
+        changes.remove_dependency_mismatches(tcx, old_def_id);
+    }
+}
+
+/// Collect the external crates whose types or traits appear in an item's signature, by name.
+fn signature_crates(tcx: TyCtxt, res: Res) -> BTreeMap<Symbol, CrateNum> {
+    use rustc_hir::def::DefKind::*;
+    use rustc_middle::ty::{subst::GenericArgKind, PredicateKind};
+
+    let (kind, def_id) = match res {
+        Res::Def(kind, def_id) => (kind, def_id),
+        _ => return BTreeMap::new(),
+    };
+    let mut types = Vec::new();
+    let mut def_ids = Vec::new();
+
+    match kind {
+        Fn | AssocFn => types.extend(tcx.fn_sig(def_id).skip_binder().inputs_and_output),
+        Const | AssocConst | Static | TyAlias => types.push(tcx.type_of(def_id)),
+        Struct | Union | Enum => types.extend(
+            tcx.adt_def(def_id)
+                .all_fields()
+                .filter(|field| field.vis == Public)
+                .map(|field| tcx.type_of(field.did)),
+        ),
+        _ => (),
+    }
+
+    if let Fn | AssocFn | Struct | Union | Enum | Trait | TyAlias = kind {
+        for (pred, _) in tcx.predicates_of(def_id).predicates {
+            if let PredicateKind::Trait(trait_pred, _) = pred.kind().skip_binder() {
+                def_ids.push(trait_pred.def_id());
+            }
+        }
+    }
+
+    for ty in types {
+        for arg in ty.walk() {
+            if let GenericArgKind::Type(ty) = arg.unpack() {
+                match *ty.kind() {
+                    TyKind::Adt(adt_def, _) => def_ids.push(adt_def.did),
+                    TyKind::Foreign(did) => def_ids.push(did),
+                    TyKind::Dynamic(preds, _) => def_ids.extend(preds.principal_def_id()),
+                    TyKind::Projection(proj) => def_ids.push(proj.item_def_id),
+                    _ => (),
+                }
+            }
+        }
+    }
+
+    def_ids
+        .into_iter()
+        .filter(|did| !did.is_local() && did.krate != def_id.krate)
+        .map(|did| (tcx.crate_name(did.krate), did.krate))
+        .collect()
+}
+
 /// Compare the implementations of all matching traits.
 fn diff_trait_impls<'tcx>(
     changes: &mut ChangeSet<'tcx>,
     id_mapping: &IdMapping,
     tcx: TyCtxt<'tcx>,
+    restricted: bool,
 ) {
     debug!("diffing trait impls");
 
     let to_new = TranslationContext::target_new(tcx, id_mapping, false);
     let to_old = TranslationContext::target_old(tcx, id_mapping, false);
+    let is_excluded = |did| id_mapping.is_excluded(tcx, did);
 
     // NOTE: Ignore for now core::marker::Structural{Eq, PartialEq} since
     // these are impl'd via *Eq traits but can we want users to see regular
@@ -1163,6 +2417,16 @@ fn diff_trait_impls<'tcx>(
             continue;
         }
 
+        if restricted
+            && !impl_in_scope(tcx, *old_impl_def_id, |did| id_mapping.contains_old_id(did))
+        {
+            continue;
+        }
+
+        if impl_in_scope(tcx, *old_impl_def_id, &is_excluded) {
+            continue;
+        }
+
         if !match_trait_impl(tcx, &to_new, *old_impl_def_id) {
             changes.new_change_impl(
                 *old_impl_def_id,
@@ -1187,6 +2451,16 @@ fn diff_trait_impls<'tcx>(
             continue;
         }
 
+        if restricted
+            && !impl_in_scope(tcx, *new_impl_def_id, |did| id_mapping.contains_new_id(did))
+        {
+            continue;
+        }
+
+        if impl_in_scope(tcx, *new_impl_def_id, &is_excluded) {
+            continue;
+        }
+
         if !match_trait_impl(tcx, &to_old, *new_impl_def_id) {
             changes.new_change_impl(
                 *new_impl_def_id,
@@ -1198,6 +2472,24 @@ fn diff_trait_impls<'tcx>(
     }
 }
 
+/// Check whether a trait impl concerns an item, i.e. its trait or self type, for which the
+/// given predicate holds.
+///
+/// This is used to restrict the analysis of trait impls to the items reachable from the paths
+/// requested, and to skip impls of excluded items.
+fn impl_in_scope<F>(tcx: TyCtxt, impl_def_id: DefId, contains: F) -> bool
+where
+    F: Fn(DefId) -> bool,
+{
+    let trait_def_id = tcx.impl_trait_ref(impl_def_id).unwrap().def_id;
+
+    contains(trait_def_id)
+        || tcx
+            .type_of(impl_def_id)
+            .ty_adt_def()
+            .map_or(false, |adt_def| contains(adt_def.did))
+}
+
 /// Compare an item pair in two inherent implementations and indicate whether the target one is
 /// compatible with the original one.
 fn match_inherent_impl<'tcx>(
@@ -1289,13 +2581,19 @@ fn match_inherent_impl<'tcx>(
             ),
             (AssocKind::Fn, AssocKind::Fn) => {
                 diff_method(changes, tcx, orig_item, target_item);
-                let orig_sig = infcx.tcx.type_of(orig_item_def_id).fn_sig(tcx);
-                let target_sig = infcx.tcx.type_of(target_item_def_id).fn_sig(tcx);
+                let (orig_sig, target_sig) = fn_sigs_for_comparison(
+                    changes,
+                    id_mapping,
+                    tcx,
+                    orig_item_def_id,
+                    target_item_def_id,
+                );
                 (tcx.mk_fn_ptr(orig_sig), tcx.mk_fn_ptr(target_sig))
             }
             _ => unreachable!(),
         };
 
+        let (orig_unsubst, target_unsubst) = (orig, target);
         let orig = compcx
             .forward_trans
             .translate_item_type(orig_item_def_id, orig);
@@ -1305,11 +2603,25 @@ fn match_inherent_impl<'tcx>(
             compcx.check_type_error(tcx, target_item_def_id, target_param_env, orig, target);
 
         if let Some(err) = error {
-            changes.add_change(
-                ChangeType::TypeChanged { error: err },
-                orig_item_def_id,
-                None,
-            );
+            let narrowed = orig.is_fn_ptr()
+                && cmp_fn_sigs(
+                    changes,
+                    id_mapping,
+                    tcx,
+                    orig_item_def_id,
+                    target_item_def_id,
+                    orig_unsubst,
+                    target_unsubst,
+                    Some((orig_impl_def_id, target_impl_def_id)),
+                );
+
+            if !narrowed {
+                changes.add_change(
+                    ChangeType::TypeChanged { error: err },
+                    orig_item_def_id,
+                    None,
+                );
+            }
         } else {
             // check the bounds if no type error has been found
             compcx.check_bounds_bidirectional(
@@ -1326,6 +2638,40 @@ fn match_inherent_impl<'tcx>(
     })
 }
 
+/// Find a trait impl in the target crate providing a method that has no inherent counterpart.
+///
+/// The impl needs to be for the ADT the given inherent entry belongs to and must not have a
+/// match in the original crate. If such an impl is found, the `DefId` of its trait is returned.
+fn find_moved_method<'a, 'tcx>(
+    tcx: TyCtxt<'tcx>,
+    trans: &TranslationContext<'a, 'tcx>,
+    target_crate: CrateNum,
+    target_item: &InherentEntry,
+) -> Option<DefId> {
+    use rustc_middle::ty::AssocKind;
+
+    if target_item.kind != AssocKind::Fn {
+        return None;
+    }
+
+    tcx.all_trait_implementations(target_crate)
+        .iter()
+        .map(|(impl_def_id, _)| *impl_def_id)
+        .filter(|impl_def_id| match tcx.type_of(*impl_def_id).kind() {
+            TyKind::Adt(def, _) => def.did == target_item.parent_def_id,
+            _ => false,
+        })
+        .filter(|impl_def_id| {
+            tcx.associated_item_def_ids(*impl_def_id)
+                .iter()
+                .map(|item_def_id| tcx.associated_item(*item_def_id))
+                .any(|item| item.kind == AssocKind::Fn && item.ident.name == target_item.name)
+        })
+        .filter(|impl_def_id| is_impl_trait_public(tcx, *impl_def_id))
+        .find(|impl_def_id| !match_trait_impl(tcx, trans, *impl_def_id))
+        .map(|impl_def_id| tcx.impl_trait_ref(impl_def_id).unwrap().def_id)
+}
+
 /// Compare two implementations and indicate whether the target one is compatible with the
 /// original one.
 fn match_trait_impl<'a, 'tcx>(
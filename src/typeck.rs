@@ -4,7 +4,7 @@
 //! for code reuse across analysis steps.
 
 use crate::{
-    changes::ChangeSet,
+    changes::{ChangeSet, ChangeType},
     mapping::IdMapping,
     translate::{InferenceCleanupFolder, TranslationContext},
 };
@@ -18,9 +18,11 @@ use rustc_middle::{
         error::TypeError,
         fold::TypeFoldable,
         subst::{GenericArg, InternalSubsts, SubstsRef},
-        GenericParamDefKind, ParamEnv, Predicate, PredicateKind, TraitRef, Ty, TyCtxt,
+        GenericParamDefKind, ParamEnv, PolyFnSig, Predicate, PredicateKind, TraitRef, Ty, TyCtxt,
+        TyKind,
     },
 };
+use rustc_span::symbol::Symbol;
 use rustc_trait_selection::traits::FulfillmentContext;
 
 /// The context in which bounds analysis happens.
@@ -267,6 +269,108 @@ impl<'a, 'tcx> TypeComparisonContext<'a, 'tcx> {
         }
     }
 
+    /// Check for mismatches in the parameter and return types of a pair of fn signatures.
+    ///
+    /// The parameters are compared in order, so that inference variables are resolved
+    /// consistently across them. Each of them is compared as part of a fn pointer type with the
+    /// binder of its signature, so that late-bound regions are handled like in the comparison of
+    /// whole signatures. Changes to parameter types are returned along with the parameter's
+    /// index. Since region constraints are not resolved, this is meant to narrow down an error
+    /// already found by `check_type_error` in a separate inference context. The `Self` types of
+    /// methods can be passed to be unified beforehand.
+    pub fn check_fn_sig_errors<'tcx2>(
+        &self,
+        lift_tcx: TyCtxt<'tcx2>,
+        target_param_env: ParamEnv<'tcx>,
+        self_tys: Option<(Ty<'tcx>, Ty<'tcx>)>,
+        orig: PolyFnSig<'tcx>,
+        target: PolyFnSig<'tcx>,
+        target_substs: SubstsRef<'tcx>,
+    ) -> Vec<(ChangeType<'tcx2>, Option<usize>)> {
+        use rustc_middle::ty::{subst::Subst, Lift};
+
+        let tcx = self.infcx.tcx;
+        let lift = |ty: Ty<'tcx>| ty.lift_to_tcx(lift_tcx).unwrap();
+
+        // the types reported keep their late-bound regions, which are printed by name, if any
+        let orig_inputs = orig.skip_binder().inputs();
+        let target_inputs = target.skip_binder().inputs();
+
+        if orig_inputs.len() != target_inputs.len() {
+            let change_type = ChangeType::FnArgCountChanged {
+                old: orig_inputs.len(),
+                new: target_inputs.len(),
+            };
+
+            return vec![(change_type, None)];
+        }
+
+        // a fn pointer type taking only the given parameter, or only returning the return type
+        let part = |sig: PolyFnSig<'tcx>, input: Option<usize>| {
+            tcx.mk_fn_ptr(sig.map_bound(|sig| {
+                let (inputs, output) = match input {
+                    Some(index) => (vec![sig.inputs()[index]], tcx.mk_unit()),
+                    None => (Vec::new(), sig.output()),
+                };
+
+                tcx.mk_fn_sig(
+                    inputs.into_iter(),
+                    output,
+                    sig.c_variadic,
+                    sig.unsafety,
+                    sig.abi,
+                )
+            }))
+        };
+
+        let target_sig = target.subst(tcx, target_substs);
+
+        let mismatch = |orig_ty: Ty<'tcx>, target_ty: Ty<'tcx>| {
+            self.infcx
+                .commit_if_ok(|snapshot| {
+                    let select = self
+                        .infcx
+                        .at(&ObligationCause::dummy(), target_param_env)
+                        .eq(orig_ty, target_ty)
+                        .map(|_| ());
+                    let leak_check = self.infcx.leak_check(false, snapshot);
+                    select.and(leak_check)
+                })
+                .is_err()
+        };
+
+        if let Some((orig_self, target_self)) = self_tys {
+            if mismatch(orig_self, target_self) {
+                return Vec::new();
+            }
+        }
+
+        let mut found = Vec::new();
+
+        for index in 0..orig_inputs.len() {
+            if mismatch(part(orig, Some(index)), part(target_sig, Some(index))) {
+                let change_type = ChangeType::FnArgTypeChanged {
+                    index,
+                    old: lift(orig_inputs[index]),
+                    new: lift(target_inputs[index]),
+                };
+
+                found.push((change_type, Some(index)));
+            }
+        }
+
+        if mismatch(part(orig, None), part(target_sig, None)) {
+            let change_type = ChangeType::FnReturnTypeChanged {
+                old: lift(orig.skip_binder().output()),
+                new: lift(target.skip_binder().output()),
+            };
+
+            found.push((change_type, None));
+        }
+
+        found
+    }
+
     /// Check for trait bound mismatches in a pair of items.
     pub fn check_bounds_error<'tcx2>(
         &self,
@@ -298,6 +402,26 @@ impl<'a, 'tcx> TypeComparisonContext<'a, 'tcx> {
         })
     }
 
+    /// Elaborate a translated `ParamEnv` in the context of the crate it has been translated to.
+    ///
+    /// The traits referenced by translated bounds can have different supertraits than their
+    /// originals, which need to be taken into account when checking the bounds of an item.
+    /// Trait definitions are exempt, as their own supertraits are the bounds being compared.
+    fn elaborate_param_env(&self, param_env: ParamEnv<'tcx>) -> ParamEnv<'tcx> {
+        use rustc_trait_selection::traits::elaborate_predicates;
+
+        if self.checking_trait_def {
+            return param_env;
+        }
+
+        let tcx = self.infcx.tcx;
+        let preds: Vec<_> = elaborate_predicates(tcx, param_env.caller_bounds().iter())
+            .map(|obligation| obligation.predicate)
+            .collect();
+
+        ParamEnv::new(tcx.intern_predicates(&preds), param_env.reveal())
+    }
+
     /// Check the bounds on an item in both directions and register changes found.
     pub fn check_bounds_bidirectional<'tcx2>(
         &self,
@@ -308,7 +432,9 @@ impl<'a, 'tcx> TypeComparisonContext<'a, 'tcx> {
         orig_substs: SubstsRef<'tcx>,
         target_substs: SubstsRef<'tcx>,
     ) {
-        use crate::changes::ChangeType::{BoundsLoosened, BoundsTightened};
+        use crate::changes::ChangeType::{
+            BoundsLoosened, BoundsTightened, SizedBoundAdded, SizedBoundRemoved,
+        };
 
         let tcx = self.infcx.tcx;
 
@@ -317,7 +443,7 @@ impl<'a, 'tcx> TypeComparisonContext<'a, 'tcx> {
             .translate_param_env(orig_def_id, tcx.param_env(orig_def_id));
 
         let orig_param_env = if let Some(env) = orig_param_env {
-            env
+            self.elaborate_param_env(env)
         } else {
             return;
         };
@@ -326,7 +452,10 @@ impl<'a, 'tcx> TypeComparisonContext<'a, 'tcx> {
             self.check_bounds_error(lift_tcx, orig_param_env, target_def_id, target_substs)
         {
             for err in errors {
-                let err_type = BoundsTightened { pred: err };
+                let err_type = match sized_param(lift_tcx, err) {
+                    Some(param) => SizedBoundAdded { param },
+                    None => BoundsTightened { pred: err },
+                };
 
                 changes.add_change(err_type, orig_def_id, None);
             }
@@ -337,7 +466,7 @@ impl<'a, 'tcx> TypeComparisonContext<'a, 'tcx> {
             .translate_param_env(target_def_id, tcx.param_env(target_def_id));
 
         let target_param_env = if let Some(env) = target_param_env {
-            env
+            self.elaborate_param_env(env)
         } else {
             return;
         };
@@ -346,9 +475,15 @@ impl<'a, 'tcx> TypeComparisonContext<'a, 'tcx> {
             self.check_bounds_error(lift_tcx, target_param_env, orig_def_id, orig_substs)
         {
             for err in errors {
-                let err_type = BoundsLoosened {
-                    pred: err,
-                    trait_def: self.checking_trait_def,
+                let err_type = match sized_param(lift_tcx, err) {
+                    Some(param) => SizedBoundRemoved {
+                        param,
+                        trait_def: self.checking_trait_def,
+                    },
+                    None => BoundsLoosened {
+                        pred: err,
+                        trait_def: self.checking_trait_def,
+                    },
                 };
 
                 changes.add_change(err_type, orig_def_id, None);
@@ -356,3 +491,21 @@ impl<'a, 'tcx> TypeComparisonContext<'a, 'tcx> {
         }
     }
 }
+
+/// Get the name of the type parameter a predicate requires to be `Sized`, if any.
+///
+/// Such predicates are implicit unless the parameter is relaxed using `?Sized`, so they are
+/// reported separately from other bounds.
+fn sized_param(tcx: TyCtxt, pred: Predicate) -> Option<Symbol> {
+    let sized_did = tcx.lang_items().sized_trait()?;
+
+    match pred.kind().skip_binder() {
+        PredicateKind::Trait(trait_pred, _) if trait_pred.def_id() == sized_did => {
+            match *trait_pred.self_ty().kind() {
+                TyKind::Param(param) => Some(param.name),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,35 @@
+pub struct Generic<T> {
+    pub field: T,
+}
+
+impl<T> Generic<T> {
+    pub fn get(&self) -> &T {
+        &self.field
+    }
+}
+
+pub type Abc = Generic<u8>;
+
+pub type Bcd = Generic<u16>;
+
+pub type Cde = (u8,);
+
+pub struct Def {
+    pub field: u16,
+}
+
+pub struct Efg {
+    pub field: u16,
+}
+
+pub fn abc(_: Abc) {}
+
+pub struct Tuple<T>(pub T);
+
+pub struct Unit;
+
+pub type Fgh = Tuple<u8>;
+
+pub type Ghi = Unit;
+
+pub struct Hij(pub u16);
@@ -0,0 +1,47 @@
+pub struct Generic<T> {
+    pub field: T,
+}
+
+impl<T> Generic<T> {
+    pub fn get(&self) -> &T {
+        &self.field
+    }
+}
+
+pub struct Abc {
+    pub field: u8,
+}
+
+impl Abc {
+    pub fn get(&self) -> &u8 {
+        &self.field
+    }
+}
+
+pub struct Bcd {
+    pub field: u8,
+}
+
+pub struct Cde {
+    pub field: u8,
+}
+
+pub type Def = Generic<u16>;
+
+struct Inner {
+    pub field: u16,
+}
+
+pub type Efg = Inner;
+
+pub fn abc(_: Abc) {}
+
+pub struct Tuple<T>(pub T);
+
+pub struct Unit;
+
+pub struct Fgh(pub u8);
+
+pub struct Ghi;
+
+pub type Hij = Tuple<u16>;
@@ -0,0 +1,21 @@
+#![feature(register_tool)]
+#![register_tool(semverver)]
+
+pub struct Config {
+    pub verbose: bool,
+}
+
+impl Config {
+    #[semverver::allow(breaking, reason = "loading requires a path now")]
+    pub fn load(_path: &str) -> Config {
+        Config { verbose: false }
+    }
+}
+
+#[semverver::allow(breaking, reason = "nobody used the old signature")]
+pub fn parse(_: &str, _: bool) {}
+
+#[semverver::allow(technically_breaking)]
+pub fn render(_: &str, _: u8) {}
+
+pub fn flush(_: bool) {}
@@ -0,0 +1,15 @@
+pub struct Config {
+    pub verbose: bool,
+}
+
+impl Config {
+    pub fn load() -> Config {
+        Config { verbose: false }
+    }
+}
+
+pub fn parse(_: &str) {}
+
+pub fn render(_: &str) {}
+
+pub fn flush() {}
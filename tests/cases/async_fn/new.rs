@@ -0,0 +1,54 @@
+// edition:2018
+use std::future::Future;
+use std::rc::Rc;
+
+pub struct Abc(u8);
+
+impl Abc {
+    pub fn abc(&self) -> impl Future<Output = u8> + '_ {
+        async move { self.0 }
+    }
+
+    pub async fn bcd(&self) -> u8 {
+        self.0
+    }
+}
+
+pub fn cde(a: u8) -> impl Future<Output = u8> {
+    async move { a }
+}
+
+pub async fn def(a: u8) -> u8 {
+    let rc = Rc::new(a);
+    async {}.await;
+    *rc
+}
+
+pub fn efg(a: &u8) -> impl Future<Output = u8> {
+    let a = *a;
+    async move { a }
+}
+
+pub fn fgh(a: u8) -> impl Future<Output = u16> {
+    async move { a as u16 }
+}
+
+pub async fn ghi(a: &u8) -> u8 {
+    *a
+}
+
+pub fn hij<T>(t: T) -> impl Future<Output = T> {
+    async move { t }
+}
+
+pub fn ijk(a: u8) -> impl Future<Output = u8> {
+    async move { a }
+}
+
+pub fn jkl(a: u8) -> impl Future<Output = u8> {
+    std::future::ready(a)
+}
+
+pub fn klm(a: u8) -> impl Future<Output = u8> + Unpin {
+    std::future::ready(a)
+}
@@ -0,0 +1,51 @@
+// edition:2018
+use std::future::Future;
+
+pub struct Abc(u8);
+
+impl Abc {
+    pub async fn abc(&self) -> u8 {
+        self.0
+    }
+
+    pub fn bcd(&self) -> impl Future<Output = u8> + '_ {
+        async move { self.0 }
+    }
+}
+
+pub async fn cde(a: u8) -> u8 {
+    a
+}
+
+pub async fn def(a: u8) -> u8 {
+    a
+}
+
+pub async fn efg(a: &u8) -> u8 {
+    *a
+}
+
+pub fn fgh(a: u8) -> impl Future<Output = u8> {
+    async move { a }
+}
+
+pub fn ghi(a: &u8) -> impl Future<Output = u8> {
+    let a = *a;
+    async move { a }
+}
+
+pub async fn hij<T>(t: T) -> T {
+    t
+}
+
+pub fn ijk(a: u8) -> impl Future<Output = u8> + Unpin {
+    std::future::ready(a)
+}
+
+pub fn jkl(a: u8) -> impl Future<Output = u8> + Unpin {
+    std::future::ready(a)
+}
+
+pub async fn klm(a: u8) -> u8 {
+    a
+}
@@ -0,0 +1,12 @@
+pub struct Config {
+    pub verbose: bool,
+    pub quiet: bool,
+}
+
+pub fn parse(_: &str, _: bool) {}
+
+pub fn render(_: &str) {}
+
+pub fn flush(_: bool) {}
+
+pub fn clear() {}
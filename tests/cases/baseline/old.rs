@@ -0,0 +1,11 @@
+pub struct Config {
+    pub verbose: bool,
+}
+
+pub fn parse(_: &str) {}
+
+pub fn render(_: &str) {}
+
+pub fn flush() {}
+
+pub fn reset() {}
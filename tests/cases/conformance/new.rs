@@ -0,0 +1,33 @@
+pub struct Parser {
+    pub strict: bool,
+}
+
+impl Parser {
+    pub fn new() -> Parser {
+        Parser { strict: false }
+    }
+
+    pub fn parse(&self, _input: &str) -> Result<Value, Error> {
+        Err(Error)
+    }
+
+    pub fn parse_fast(&self, _input: &[u8]) -> Result<Value, Error> {
+        Err(Error)
+    }
+}
+
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+}
+
+pub struct Error;
+
+pub fn to_string(_value: &Value) -> String {
+    String::new()
+}
+
+pub fn to_vec(_value: &Value) -> Vec<u8> {
+    Vec::new()
+}
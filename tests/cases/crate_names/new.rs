@@ -0,0 +1,3 @@
+pub fn abc() {}
+
+pub fn bcd() {}
@@ -0,0 +1,2 @@
+// names: foo foo-fork
+pub fn abc() {}
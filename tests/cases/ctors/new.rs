@@ -0,0 +1,19 @@
+#![allow(dead_code)]
+pub struct Abc(pub u8, ());
+
+#[non_exhaustive]
+pub struct Bcd(pub u8);
+
+pub struct Cde(pub u8);
+
+pub struct Def {}
+
+pub struct Efg(pub u8);
+
+pub struct Fgh(pub u8);
+
+pub enum Ghi {
+    #[non_exhaustive]
+    A(u8),
+    B,
+}
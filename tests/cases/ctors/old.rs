@@ -0,0 +1,19 @@
+#![allow(dead_code)]
+pub struct Abc(pub u8);
+
+pub struct Bcd(pub u8);
+
+pub struct Cde(pub u8, pub u8);
+
+pub struct Def;
+
+pub struct Efg(u8);
+
+pub struct Fgh {
+    pub field: u8,
+}
+
+pub enum Ghi {
+    A(u8),
+    B,
+}
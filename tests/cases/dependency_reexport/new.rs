@@ -0,0 +1,7 @@
+extern crate dep;
+
+pub use dep::{make_widget, Render, Widget};
+
+pub fn local_widget() -> Widget {
+    make_widget()
+}
@@ -0,0 +1,19 @@
+pub struct Widget {
+    pub size: u16,
+}
+
+impl Widget {
+    pub fn resize(&mut self, size: u16) {
+        self.size = size;
+    }
+}
+
+pub fn make_widget() -> Widget {
+    Widget { size: 0 }
+}
+
+pub trait Render {
+    fn render(&self) -> String;
+
+    fn render_to(&self, out: &mut String);
+}
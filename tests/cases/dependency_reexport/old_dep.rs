@@ -0,0 +1,17 @@
+pub struct Widget {
+    pub size: u8,
+}
+
+impl Widget {
+    pub fn resize(&mut self, size: u8) {
+        self.size = size;
+    }
+}
+
+pub fn make_widget() -> Widget {
+    Widget { size: 0 }
+}
+
+pub trait Render {
+    fn render(&self) -> String;
+}
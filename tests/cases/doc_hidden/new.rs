@@ -0,0 +1,42 @@
+#[doc(hidden)]
+pub mod __macro_support {
+    pub fn helper(_: u8) {}
+
+    pub struct Internal;
+}
+
+#[doc(hidden)]
+pub fn hidden_fn(_: u16) {}
+
+pub struct Visible;
+
+impl Visible {
+    #[doc(hidden)]
+    pub fn hidden_method(&self, _: u8) {}
+
+    pub fn method(&self) {}
+}
+
+pub fn uses_internal(_: __macro_support::Internal) {}
+
+pub fn breaks(_: u8) {}
+
+pub enum Kind {
+    A,
+    #[doc(hidden)]
+    __Unknown,
+}
+
+pub struct Config {
+    pub level: u8,
+}
+
+pub trait Handler {
+    fn handle(&self);
+
+    #[doc(hidden)]
+    fn __private_api(&self, _: u8) {}
+
+    #[doc(hidden)]
+    fn __macro_api(&self) {}
+}
@@ -0,0 +1,41 @@
+#[doc(hidden)]
+pub mod __macro_support {
+    pub fn helper() {}
+
+    pub struct Internal;
+}
+
+#[doc(hidden)]
+pub fn hidden_fn(_: u8) {}
+
+pub struct Visible;
+
+impl Visible {
+    #[doc(hidden)]
+    pub fn hidden_method(&self) {}
+
+    pub fn method(&self) {}
+}
+
+pub fn uses_internal(_: __macro_support::Internal) {}
+
+pub fn breaks() {}
+
+pub enum Kind {
+    A,
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+pub struct Config {
+    pub level: u8,
+    #[doc(hidden)]
+    pub __private: (),
+}
+
+pub trait Handler {
+    fn handle(&self);
+
+    #[doc(hidden)]
+    fn __private_api(&self) {}
+}
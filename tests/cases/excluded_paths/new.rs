@@ -0,0 +1,22 @@
+pub mod experimental {
+    pub struct Draft {
+        pub field: u16,
+    }
+}
+
+#[doc(hidden)]
+pub mod __private {
+    pub fn helper(_: u8) {}
+
+    pub struct Token;
+}
+
+pub struct Hidden(pub u16);
+
+pub fn uses_draft(_: experimental::Draft) {}
+
+pub fn stable(_: u8) {}
+
+pub fn token() -> __private::Token {
+    __private::Token
+}
@@ -0,0 +1,37 @@
+// exclude: experimental::*,*__private*,Hidden
+pub mod experimental {
+    pub struct Draft {
+        pub field: u8,
+    }
+
+    pub fn unstable() {}
+}
+
+#[doc(hidden)]
+pub mod __private {
+    pub fn helper() {}
+
+    pub struct Token;
+
+    impl Clone for Token {
+        fn clone(&self) -> Token {
+            Token
+        }
+    }
+}
+
+pub struct Hidden(pub u8);
+
+impl Clone for Hidden {
+    fn clone(&self) -> Hidden {
+        Hidden(self.0)
+    }
+}
+
+pub fn uses_draft(_: experimental::Draft) {}
+
+pub fn stable() {}
+
+pub fn token() -> __private::Token {
+    __private::Token
+}
@@ -0,0 +1,28 @@
+pub struct Abc;
+
+pub fn abc(a: u8, b: u32, c: u16) -> u8 {
+    a + b as u8 + c as u8
+}
+
+pub fn bcd<A, B>(a: A, _: B) -> A {
+    a
+}
+
+pub fn cde(a: &'static str, b: u8) -> &str {
+    let _ = b;
+    a
+}
+
+pub fn def(a: u8, b: u8) {
+    let _ = (a, b);
+}
+
+impl Abc {
+    pub fn efg(&self, a: u16) -> u16 {
+        a
+    }
+}
+
+pub fn fgh<A>(a: A, _: impl Fn()) -> A {
+    a
+}
@@ -0,0 +1,28 @@
+pub struct Abc;
+
+pub fn abc(a: u8, b: u16, c: u32) -> u8 {
+    a + b as u8 + c as u8
+}
+
+pub fn bcd<A>(a: A, _: A) -> A {
+    a
+}
+
+pub fn cde(a: &str, b: u8) -> &str {
+    let _ = b;
+    a
+}
+
+pub fn def(a: u8) {
+    let _ = a;
+}
+
+impl Abc {
+    pub fn efg(&self, a: u8) -> u8 {
+        a
+    }
+}
+
+pub fn fgh<A>(a: A) -> A {
+    a
+}
@@ -0,0 +1,25 @@
+pub fn abc<'a>(a: &'a u8) -> &'a u8 {
+    a
+}
+
+pub fn bcd<'a>(a: &'a u8) -> &'a u8
+where
+    'a: 'a,
+{
+    a
+}
+
+pub fn cde<'a, 'b>(a: &'a u8, _: &'b u8) -> &'a u8 {
+    a
+}
+
+pub trait Abc {
+    fn def<'a>(&self, a: &'a u8) -> &'a u8;
+}
+
+pub fn efg<'a, 'b>(_: &'a u8, _: fn(&'b u8))
+where
+    'a: 'a,
+    'b: 'b,
+{
+}
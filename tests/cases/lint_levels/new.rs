@@ -0,0 +1,11 @@
+pub enum Mode {
+    Fast,
+    Slow,
+    Adaptive,
+}
+
+pub fn run(_: Mode, _: u8) {}
+
+pub fn stop() {}
+
+pub fn pause() {}
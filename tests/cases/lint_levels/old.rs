@@ -0,0 +1,9 @@
+// lints: semver::enum_variant_added=allow,path_added=deny,fn_arg_count_changed=warn
+pub enum Mode {
+    Fast,
+    Slow,
+}
+
+pub fn run(_: Mode) {}
+
+pub fn stop() {}
@@ -0,0 +1,25 @@
+pub trait Abc {
+    fn abc(&self) -> u8;
+}
+
+pub struct Def;
+
+impl Def {
+    pub fn bcd(&self) -> u8 {
+        0
+    }
+}
+
+impl Abc for Def {
+    fn abc(&self) -> u8 {
+        0
+    }
+}
+
+pub struct Efg;
+
+impl Efg {
+    pub fn abc(&self) -> u8 {
+        0
+    }
+}
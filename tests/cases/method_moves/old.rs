@@ -0,0 +1,23 @@
+pub trait Abc {
+    fn abc(&self) -> u8;
+}
+
+pub struct Def;
+
+impl Def {
+    pub fn abc(&self) -> u8 {
+        0
+    }
+
+    pub fn bcd(&self) -> u8 {
+        0
+    }
+}
+
+pub struct Efg;
+
+impl Abc for Efg {
+    fn abc(&self) -> u8 {
+        0
+    }
+}
@@ -0,0 +1,10 @@
+// policy: lenient
+pub struct Config {
+    pub verbose: bool,
+}
+
+pub trait Render {
+    fn render(&self);
+}
+
+pub fn run() {}
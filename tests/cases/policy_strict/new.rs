@@ -0,0 +1,23 @@
+pub struct Config {
+    pub verbose: bool,
+}
+
+impl Config {
+    pub fn new() -> Config {
+        Config { verbose: false }
+    }
+}
+
+pub trait Render {
+    fn render(&self);
+
+    fn clear(&self) {}
+}
+
+impl Render for Config {
+    fn render(&self) {}
+}
+
+pub fn run() {}
+
+pub fn stop() {}
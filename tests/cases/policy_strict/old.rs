@@ -0,0 +1,10 @@
+// policy: strict
+pub struct Config {
+    pub verbose: bool,
+}
+
+pub trait Render {
+    fn render(&self);
+}
+
+pub fn run() {}
@@ -0,0 +1,17 @@
+extern crate dep;
+
+pub fn make() -> dep::Bytes {
+    dep::Bytes(Vec::new())
+}
+
+pub struct Buffer {
+    pub inner: dep::Bytes,
+}
+
+pub fn encode_all<T: dep::Encode>(_: &[T]) {}
+
+pub fn unrelated(_: u8) {}
+
+pub fn size(_: &dep::Bytes) -> u16 {
+    0
+}
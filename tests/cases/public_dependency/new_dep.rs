@@ -0,0 +1,6 @@
+// version: 2.0.0
+pub struct Bytes(pub Vec<u8>);
+
+pub trait Encode {
+    fn encode(&self) -> Bytes;
+}
@@ -17,3 +17,5 @@ pub fn def(_: bool) { }
 pub fn efg(_: &str) { }
 
 pub fn fgh(_: &'static str) { }
+
+pub fn ghi<'a>(a: &'a bool, _: &'a u16) -> &'a bool { a }
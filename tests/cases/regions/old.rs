@@ -17,3 +17,5 @@ pub fn def(_: &bool) { }
 pub fn efg(_: &'static str) { }
 
 pub fn fgh(_: &str) { }
+
+pub fn ghi<'a>(a: &'a bool, _: &'a u8) -> &'a bool { a }
@@ -0,0 +1,35 @@
+pub mod net {
+    pub struct Socket {
+        pub port: u32,
+    }
+
+    pub fn connect(_port: u16, _timeout: u64) -> Socket {
+        Socket { port: 0 }
+    }
+
+    pub fn listen() {}
+}
+
+pub mod fs {
+    pub struct File(pub u8);
+
+    pub struct Dir;
+
+    impl Dir {
+        pub fn open(_path: &str) -> Dir {
+            Dir
+        }
+    }
+}
+
+pub mod io {}
+
+pub struct Unrelated(u8);
+
+mod hidden {
+    pub struct Thing(pub u16);
+}
+
+pub mod shown {
+    pub fn shown() {}
+}
@@ -0,0 +1,50 @@
+// paths: net,crate::fs::File,io::gone,hidden::Thing,shown::shown
+pub mod net {
+    pub struct Socket {
+        pub port: u16,
+    }
+
+    impl Clone for Socket {
+        fn clone(&self) -> Socket {
+            Socket { port: self.port }
+        }
+    }
+
+    pub fn connect(_port: u16) -> Socket {
+        Socket { port: 0 }
+    }
+}
+
+pub mod fs {
+    pub struct File;
+
+    pub struct Dir;
+
+    impl Dir {
+        pub fn open() -> Dir {
+            Dir
+        }
+    }
+}
+
+pub mod io {
+    pub fn gone() {}
+
+    pub fn other() {}
+}
+
+pub struct Unrelated;
+
+impl Clone for Unrelated {
+    fn clone(&self) -> Unrelated {
+        Unrelated
+    }
+}
+
+mod hidden {
+    pub struct Thing(pub u8);
+}
+
+mod shown {
+    pub fn shown() {}
+}
@@ -0,0 +1,13 @@
+pub fn abc<T>(_: &T) {}
+
+pub fn bcd<T: ?Sized>(_: &T) {}
+
+pub struct Cde<T>(pub Box<T>);
+
+pub trait Def<T: ?Sized> {
+    fn def(&self, _: &T);
+}
+
+pub trait Efg<T> {
+    fn efg(&self, _: &T);
+}
@@ -0,0 +1,13 @@
+pub fn abc<T: ?Sized>(_: &T) {}
+
+pub fn bcd<T>(_: &T) {}
+
+pub struct Cde<T: ?Sized>(pub Box<T>);
+
+pub trait Def<T> {
+    fn def(&self, _: &T);
+}
+
+pub trait Efg<T: ?Sized> {
+    fn efg(&self, _: &T);
+}
@@ -0,0 +1,33 @@
+mod private {
+    pub trait Sealed {}
+}
+
+pub trait AbcBase {
+    fn abc(&self) -> u8;
+
+    fn cde(&self) -> u16;
+}
+
+pub trait Abc: AbcBase {
+    fn bcd(&self);
+}
+
+pub trait BcdBase {
+    fn abc(&self) -> u8;
+}
+
+pub trait Bcd: BcdBase + private::Sealed {}
+
+pub trait Cde {}
+
+pub trait Def {
+    fn abc(&self) -> u8;
+}
+
+pub trait EfgBase {
+    fn efg(&self, x: u16);
+}
+
+pub trait Efg: EfgBase {
+    fn fgh(&self);
+}
@@ -0,0 +1,25 @@
+mod private {
+    pub trait Sealed {}
+}
+
+pub trait Abc {
+    fn abc(&self) -> u8;
+
+    fn bcd(&self);
+
+    fn cde(&self) -> u8;
+}
+
+pub trait Bcd: private::Sealed {
+    fn abc(&self) -> u8;
+}
+
+pub trait Cde {
+    fn abc(&self) -> u8;
+}
+
+pub trait Efg {
+    fn efg(&self, x: u8);
+
+    fn fgh(&self);
+}
@@ -0,0 +1,19 @@
+use std::fmt::Debug;
+
+pub fn abc<B: Debug, A: Debug>(_: A, _: B) {}
+
+pub fn bcd(_: impl Debug) {}
+
+pub fn cde<A: Debug>(_: A) {}
+
+pub fn def<A: Debug, B: Default>(_: A) -> B {
+    B::default()
+}
+
+pub fn fgh<X: Debug, Y: Debug>(_: X, _: Y) {}
+
+pub fn ghi<A: Debug, B: Debug>(_: A, _: B) {}
+
+pub fn hij<B: Debug, A: Debug>(_: B, _: Option<A>) {}
+
+pub fn ijk<X: Debug, Y: Debug>(_: Y, _: Option<X>) {}
@@ -0,0 +1,17 @@
+use std::fmt::Debug;
+
+pub fn abc<A: Debug, B: Debug>(_: A, _: B) {}
+
+pub fn bcd<A: Debug>(_: A) {}
+
+pub fn cde(_: impl Debug) {}
+
+pub fn def<A: Debug>(_: A) {}
+
+pub fn fgh<A: Debug, B: Debug>(_: A, _: B) {}
+
+pub fn ghi<A: Debug>(_: A, _: impl Debug) {}
+
+pub fn hij<A: Debug, B: Debug>(_: A, _: Option<B>) {}
+
+pub fn ijk<A: Debug, B: Debug>(_: A, _: Option<B>) {}
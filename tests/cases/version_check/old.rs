@@ -0,0 +1,2 @@
+// check: 1.1.0
+pub fn parse(_: &str) {}
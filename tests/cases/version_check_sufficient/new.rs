@@ -0,0 +1 @@
+pub fn parse(_: &str, _: bool) {}
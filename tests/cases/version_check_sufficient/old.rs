@@ -0,0 +1,2 @@
+// check: 2.0.0
+pub fn parse(_: &str) {}
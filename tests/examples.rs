@@ -8,18 +8,97 @@ mod features {
         str,
     };
 
+    /// Get the value of a `// key:` header from the leading comment lines of a test crate.
+    fn header_of(source_path: &Path, key: &str) -> Option<String> {
+        let prefix = format!("// {}:", key);
+
+        read_to_string(source_path)
+            .expect("could not read test crate")
+            .lines()
+            .take_while(|line| line.starts_with("//"))
+            .find_map(|line| {
+                line.strip_prefix(&prefix)
+                    .map(|value| value.trim().to_owned())
+            })
+    }
+
+    /// Get the edition a test crate has to be compiled with, given by an `// edition:` header.
+    fn edition_of(source_path: &Path) -> String {
+        header_of(source_path, "edition").unwrap_or_else(|| "2015".to_owned())
+    }
+
+    /// Get the `name=version` list passed for the `dep` crate, given by a `// version:` header.
+    fn dep_versions(path: &Path, version: &str) -> String {
+        let source_path = path.join(format!("{}_dep.rs", version));
+
+        if source_path.exists() {
+            header_of(&source_path, "version")
+                .map(|v| format!("dep={}", v))
+                .unwrap_or_default()
+        } else {
+            String::new()
+        }
+    }
+
+    /// Compile the version of the `dep` crate a test crate depends on, if there is one.
+    ///
+    /// Both versions of the dependency are compiled into the test directory, so they need
+    /// distinct file names.
+    fn compile_dep(
+        path: &Path,
+        version: &str,
+        target_args: &Result<[String; 2], env::VarError>,
+    ) -> Option<String> {
+        let source_path = path.join(format!("{}_dep.rs", version));
+
+        if !source_path.exists() {
+            return None;
+        }
+
+        let dep_rlib = path
+            .join(format!("libdep-{}.rlib", version))
+            .to_str()
+            .unwrap()
+            .to_owned();
+
+        let mut cmd = Command::new("rustc");
+        cmd.args(&["--crate-type=lib", "--crate-name=dep", "-o", &dep_rlib])
+            .arg(format!("-Cmetadata={}", version))
+            .args(&["--edition", &edition_of(&source_path)])
+            .arg(source_path)
+            .env("RUST_BACKTRACE", "full")
+            .stdin(Stdio::null());
+
+        if let Ok(target_args) = target_args {
+            cmd.args(target_args);
+        }
+
+        let rustc_result = cmd.status().expect("could not run rustc on dep").success();
+        assert!(rustc_result, "couldn't compile {} dep", version);
+
+        Some(dep_rlib)
+    }
+
     fn test_example2(name: &str, path: &Path, expected_path: &Path, expected_result: bool) {
         let old_rlib = path.join("libold.rlib").to_str().unwrap().to_owned();
         let new_rlib = path.join("libnew.rlib").to_str().unwrap().to_owned();
 
         let target_args = std::env::var("TEST_TARGET").map(|t| ["--target".to_string(), t]);
 
+        let old_dep_rlib = compile_dep(path, "old", &target_args);
+        let new_dep_rlib = compile_dep(path, "new", &target_args);
+
         let mut cmd = Command::new("rustc");
         cmd.args(&["--crate-type=lib", "-o", &old_rlib])
+            .args(&["--edition", &edition_of(&path.join("old.rs"))])
             .arg(path.join("old.rs"))
             .env("RUST_BACKTRACE", "full")
             .stdin(Stdio::null());
 
+        if let Some(dep_rlib) = &old_dep_rlib {
+            cmd.args(&["--extern", &format!("dep={}", dep_rlib)]);
+        }
+
         if let Ok(target_args) = &target_args {
             cmd.args(target_args);
         }
@@ -29,10 +108,15 @@ mod features {
 
         let mut cmd = Command::new("rustc");
         cmd.args(&["--crate-type=lib", "-o", &new_rlib])
+            .args(&["--edition", &edition_of(&path.join("new.rs"))])
             .arg(path.join("new.rs"))
             .env("RUST_BACKTRACE", "full")
             .stdin(Stdio::null());
 
+        if let Some(dep_rlib) = &new_dep_rlib {
+            cmd.args(&["--extern", &format!("dep={}", dep_rlib)]);
+        }
+
         if let Ok(target_args) = &target_args {
             cmd.args(target_args);
         }
@@ -50,7 +134,6 @@ mod features {
         );
         cmd.args(&[
             "--crate-type=lib",
-            "-Zverbose",
             "--extern",
             &format!("old={}", old_rlib),
             "--extern",
@@ -63,10 +146,48 @@ mod features {
         ])
         .env("RUST_BACKTRACE", "full")
         .env("RUST_SEMVER_CRATE_VERSION", "1.0.0")
+        .env("RUST_SEMVER_OLD_DEPS", dep_versions(path, "old"))
+        .env("RUST_SEMVER_NEW_DEPS", dep_versions(path, "new"))
+        .env(
+            "RUST_SEMVER_PATHS",
+            header_of(&path.join("old.rs"), "paths").unwrap_or_default(),
+        )
+        .env(
+            "RUST_SEMVER_EXCLUDE",
+            header_of(&path.join("old.rs"), "exclude").unwrap_or_default(),
+        )
+        .env(
+            "RUST_SEMVER_LINTS",
+            header_of(&path.join("old.rs"), "lints").unwrap_or_default(),
+        )
+        .env(
+            "RUST_SEMVER_POLICY",
+            header_of(&path.join("old.rs"), "policy").unwrap_or_else(|| "rfc1105".to_owned()),
+        )
         .stdin(Stdio::null())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped());
 
+        if old_dep_rlib.is_some() || new_dep_rlib.is_some() {
+            cmd.arg(format!("-Ldependency={}", path.display()));
+        }
+
+        let baseline = path.join("baseline.json");
+        if baseline.exists() {
+            cmd.env("RUST_SEMVER_BASELINE", baseline);
+        }
+
+        if let Some(names) = header_of(&path.join("old.rs"), "names") {
+            let mut names = names.split_whitespace();
+            cmd.env("RUST_SEMVER_OLD_NAME", names.next().unwrap())
+                .env("RUST_SEMVER_NEW_NAME", names.next().unwrap());
+        }
+
+        let check = header_of(&path.join("old.rs"), "check");
+        if let Some(new_version) = &check {
+            cmd.env("RUST_SEMVER_VERIFY_VERSION", new_version);
+        }
+
         if let Ok(target_args) = &target_args {
             cmd.args(target_args);
         }
@@ -76,7 +197,35 @@ mod features {
             .unwrap()
             .contains("stdout_api_guidelines")
         {
-            cmd.env("RUST_SEMVER_API_GUIDELINES", "true");
+            cmd.env("RUST_SEMVER_POLICY", "api-guidelines");
+        }
+
+        if expected_path
+            .to_str()
+            .unwrap()
+            .contains("stdout_conformance")
+        {
+            cmd.env("RUST_SEMVER_CONFORMANCE", "true");
+        }
+
+        if expected_path
+            .to_str()
+            .unwrap()
+            .contains("stdout_doc_hidden")
+        {
+            cmd.env("RUST_SEMVER_DOC_HIDDEN_PRIVATE", "true");
+        }
+
+        if expected_path.to_str().unwrap().contains("stdout_json") {
+            cmd.env("RUST_SEMVER_JSON", "true");
+        }
+
+        if expected_path.to_str().unwrap().contains("stdout_sarif") {
+            cmd.env("RUST_SEMVER_SARIF", "true");
+        }
+
+        if expected_path.to_str().unwrap().contains("stdout_verbose") {
+            cmd.env("RUST_SEMVER_VERBOSE", "true");
         }
 
         let expected_output = read_to_string(&expected_path)
@@ -96,6 +245,10 @@ mod features {
         let output = cmd.output().expect("could not run rust-semverver");
 
         let new_output = {
+            // absolute paths, like the ones in JSON output, are made relative for reproducibility
+            let cwd = format!("{}/", env::current_dir().unwrap().display());
+            // the tool version in SARIF output changes with every release
+            let version = format!("\"version\": \"{}\"", env!("CARGO_PKG_VERSION"));
             let stdout: &str = str::from_utf8(&output.stdout)
                 .expect("could not read line from rust-semverver output")
                 .trim_end();
@@ -120,6 +273,8 @@ mod features {
                         None => line.to_string(),
                     }
                 })
+                .map(|l| l.replace(&cwd, ""))
+                .map(|l| l.replace(&version, "\"version\": \"VERSION\""))
                 .map(|l| {
                     if cfg!(target_os = "windows") {
                         l.replace('\\', "/")
@@ -161,15 +316,34 @@ mod features {
             panic!("unexpected output diff");
         }
 
+        // technically breaking changes are only reported as warnings
+        let success = matches!(output.status.code(), Some(0) | Some(3));
         assert_eq!(
-            output.status.success(),
-            expected_result,
+            success, expected_result,
             "rust-semverver returned an unexpected exit status"
         );
 
+        if check.is_some() && !expected_result {
+            assert_eq!(
+                output.status.code(),
+                Some(5),
+                "rust-semverver didn't report an insufficient version"
+            );
+        } else if !expected_result {
+            assert_eq!(
+                output.status.code(),
+                Some(4),
+                "rust-semverver didn't report breaking changes"
+            );
+        }
+
         let rm_old = std::fs::remove_file(old_rlib);
         let rm_new = std::fs::remove_file(new_rlib);
         rm_old.and(rm_new).expect("could not remove rlib files");
+
+        for dep_rlib in old_dep_rlib.iter().chain(&new_dep_rlib) {
+            std::fs::remove_file(dep_rlib).expect("could not remove dep rlib files");
+        }
     }
 
     macro_rules! test {
@@ -183,6 +357,31 @@ mod features {
                     eprintln!("api-guidelines");
                     test_example2(stringify!($name), &path, &path.join("stdout_api_guidelines"), $result);
                 }
+
+                if path.join("stdout_conformance").exists() {
+                    eprintln!("conformance");
+                    test_example2(stringify!($name), &path, &path.join("stdout_conformance"), $result);
+                }
+
+                if path.join("stdout_doc_hidden").exists() {
+                    eprintln!("doc-hidden");
+                    test_example2(stringify!($name), &path, &path.join("stdout_doc_hidden"), $result);
+                }
+
+                if path.join("stdout_json").exists() {
+                    eprintln!("json");
+                    test_example2(stringify!($name), &path, &path.join("stdout_json"), $result);
+                }
+
+                if path.join("stdout_sarif").exists() {
+                    eprintln!("sarif");
+                    test_example2(stringify!($name), &path, &path.join("stdout_sarif"), $result);
+                }
+
+                if path.join("stdout_verbose").exists() {
+                    eprintln!("verbose");
+                    test_example2(stringify!($name), &path, &path.join("stdout_verbose"), $result);
+                }
             }
         };
         ($($name:ident => $result:literal),*) => {
@@ -197,10 +396,21 @@ mod features {
         addition => true,
         addition_path => true,
         addition_use => false,
+        alias_kind_change => false,
+        allowed_changes => false,
+        async_fn => false,
+        baseline => true,
         bounds => false,
         circular => true,
+        conformance => false,
         consts => false,
+        crate_names => true,
+        ctors => false,
+        dependency_reexport => false,
+        doc_hidden => false,
         enums => false,
+        excluded_paths => false,
+        fn_sigs => false,
         func => false,
         func_local_items => true,
         infer => true,
@@ -209,21 +419,34 @@ mod features {
         issue_34 => true,
         issue_50 => true,
         kind_change => false,
+        late_bound => false,
+        lint_levels => false,
         macros => false,
+        method_moves => false,
         max_priv => true,
         mix => false,
         pathologic_paths => true,
+        policy_lenient => false,
+        policy_strict => false,
+        public_dependency => false,
+        public_dependency_same => false,
         pub_use => true,
         regions => false,
         removal => false,
         removal_path => false,
         removal_use => false,
+        restricted_paths => false,
         sealed_traits => true,
+        sized_bounds => false,
         structs => false,
+        supertrait_move => false,
         swap => true,
         traits => false,
         trait_impls => false,
         trait_objects => true,
+        turbofish => true,
         ty_alias => false,
+        version_check => false,
+        version_check_sufficient => true,
     }
 }